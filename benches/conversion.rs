@@ -0,0 +1,172 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Benchmarks for the path conversions.
+//!
+//! Run with `cargo bench`. Each benchmark converts all paths of a set, so that the results of
+//! benchmarks in the same group can be compared with each other.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use typed_path::{
+    Utf8Component, Utf8UnixPath, Utf8UnixPathBuf, Utf8WindowsComponent, Utf8WindowsPath,
+    Utf8WindowsPrefix,
+};
+use wslpath_rs::{
+    windows_to_wsl, windows_to_wsl_into, windows_to_wsl_with, wsl_capacity_hint, wsl_to_windows,
+    Options,
+};
+
+/// Windows paths that are normalized already.
+const CLEAN_WINDOWS_PATHS: &[&str] = &[
+    "C:\\Windows\\System32\\drivers\\etc\\hosts",
+    "D:\\Projects\\wslpath-rs\\src\\lib.rs",
+    "\\\\wsl.localhost\\Ubuntu\\home\\user\\.config\\nvim\\init.lua",
+    "C:\\Users\\user\\AppData\\Local\\Programs\\Microsoft VS Code\\Code.exe",
+];
+
+/// Windows paths that contain `.` or `..` components.
+const UNCLEAN_WINDOWS_PATHS: &[&str] = &[
+    "C:\\Windows\\System32\\..\\SysWOW64\\.\\drivers\\etc\\hosts",
+    "D:\\Projects\\wslpath-rs\\target\\..\\src\\lib.rs",
+    "\\\\wsl.localhost\\Ubuntu\\home\\user\\.\\.config\\..\\.config\\nvim\\init.lua",
+    "C:\\Users\\user\\..\\user\\AppData\\Local\\Programs\\Microsoft VS Code\\Code.exe",
+];
+
+/// Windows paths with different prefixes, whose WSL paths are shorter or longer than the input.
+const MIXED_WINDOWS_PATHS: &[&str] = &[
+    "C:\\Windows\\System32\\drivers\\etc\\hosts",
+    "\\\\?\\C:\\Windows\\System32\\drivers\\etc\\hosts",
+    "\\\\wsl.localhost\\Ubuntu\\home\\user\\.config\\nvim\\init.lua",
+    "\\\\?\\UNC\\wsl.localhost\\Ubuntu\\home\\user\\.config\\nvim\\init.lua",
+    "\\\\wsl$\\openSUSE-Tumbleweed\\etc\\fstab",
+    "\\\\.\\D:\\Projects\\wslpath-rs\\src\\lib.rs",
+];
+
+/// WSL paths that are normalized already.
+const CLEAN_WSL_PATHS: &[&str] = &[
+    "/mnt/c/Windows/System32/drivers/etc/hosts",
+    "/mnt/d/Projects/wslpath-rs/src/lib.rs",
+    "/mnt/c/Users/user/AppData/Local/Programs/Microsoft VS Code/Code.exe",
+];
+
+/// Convert a Windows path by pushing its components onto a `Utf8UnixPathBuf` and normalizing it
+/// afterwards, which is how [`windows_to_wsl`] worked before it wrote into a `String` directly.
+///
+/// Only drive paths and paths to the filesystem of a WSL distribution are supported.
+fn windows_to_wsl_typed_path(windows_path: &str) -> Option<String> {
+    let mut components = Utf8WindowsPath::new(windows_path).components();
+    let Some(Utf8WindowsComponent::Prefix(prefix)) = components.next() else {
+        return None;
+    };
+    let mut path = match prefix.kind() {
+        Utf8WindowsPrefix::Disk(drive) | Utf8WindowsPrefix::VerbatimDisk(drive) => {
+            Utf8UnixPathBuf::from(format!("/mnt/{}", drive.to_ascii_lowercase()))
+        }
+        Utf8WindowsPrefix::UNC(hostname, _) | Utf8WindowsPrefix::VerbatimUNC(hostname, _)
+            if hostname.eq_ignore_ascii_case("wsl.localhost") || hostname == "wsl$" =>
+        {
+            Utf8UnixPathBuf::from("/")
+        }
+        _ => return None,
+    };
+    for component in components {
+        if !matches!(component, Utf8WindowsComponent::RootDir) {
+            path.push(component.as_str());
+        }
+    }
+    Some(path.normalize().into_string())
+}
+
+/// Benchmark `convert` by converting all `paths`.
+fn bench<T>(
+    c: &mut Criterion,
+    group: &str,
+    name: &str,
+    paths: &[&str],
+    convert: impl Fn(&str) -> T,
+) {
+    c.benchmark_group(group).bench_function(name, |b| {
+        b.iter(|| {
+            for path in paths {
+                black_box(convert(black_box(path)));
+            }
+        });
+    });
+}
+
+/// Compare the conversion into a `String` with the conversion via `Utf8UnixPathBuf`.
+fn windows_to_wsl_allocation(c: &mut Criterion) {
+    for paths in [CLEAN_WINDOWS_PATHS, UNCLEAN_WINDOWS_PATHS] {
+        for path in paths {
+            assert_eq!(
+                windows_to_wsl_typed_path(path),
+                windows_to_wsl(path).ok(),
+                "{path:?}"
+            );
+        }
+    }
+    for (name, paths) in [
+        ("clean", CLEAN_WINDOWS_PATHS),
+        ("with . and ..", UNCLEAN_WINDOWS_PATHS),
+    ] {
+        let group = format!("windows_to_wsl ({name})");
+        bench(c, &group, "typed-path", paths, windows_to_wsl_typed_path);
+        bench(c, &group, "string", paths, windows_to_wsl);
+    }
+}
+
+/// Compare the conversion of clean paths, which skips normalizing them, with normalizing them
+/// afterwards.
+fn windows_to_wsl_normalization(c: &mut Criterion) {
+    let options = Options::builder().normalize(false).build();
+    let normalize = |path: &str| {
+        windows_to_wsl_with(path, &options)
+            .map(|converted| Utf8UnixPath::new(&converted).normalize().into_string())
+    };
+    for path in CLEAN_WINDOWS_PATHS {
+        assert_eq!(normalize(path), windows_to_wsl(path), "{path:?}");
+    }
+    let group = "windows_to_wsl (clean, normalization)";
+    bench(c, group, "normalize", CLEAN_WINDOWS_PATHS, normalize);
+    bench(c, group, "skip", CLEAN_WINDOWS_PATHS, windows_to_wsl);
+}
+
+/// Compare reserving the capacity for the prefix of each path with reserving the length of the
+/// path plus 4 bytes.
+fn windows_to_wsl_capacity(c: &mut Criterion) {
+    let length_hint = |path: &str| {
+        let mut converted = String::with_capacity(wsl_capacity_hint(path.len()));
+        windows_to_wsl_into(path, &mut converted).map(|()| converted)
+    };
+    for path in MIXED_WINDOWS_PATHS {
+        assert_eq!(length_hint(path), windows_to_wsl(path), "{path:?}");
+    }
+    let group = "windows_to_wsl (mixed prefixes)";
+    bench(c, group, "length + 4", MIXED_WINDOWS_PATHS, length_hint);
+    bench(c, group, "per prefix", MIXED_WINDOWS_PATHS, windows_to_wsl);
+}
+
+fn conversions(c: &mut Criterion) {
+    bench(
+        c,
+        "wsl_to_windows (clean)",
+        "string",
+        CLEAN_WSL_PATHS,
+        wsl_to_windows,
+    );
+}
+
+criterion_group!(
+    benches,
+    windows_to_wsl_allocation,
+    windows_to_wsl_normalization,
+    windows_to_wsl_capacity,
+    conversions
+);
+criterion_main!(benches);
@@ -0,0 +1,161 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion of paths that are not necessarily valid UTF-8.
+
+use crate::{windows_to_wsl_buffer, wsl_to_windows_buffer, Error, Options};
+use alloc::vec::Vec;
+
+/// Convert a Windows path that is not necessarily valid UTF-8 to a WSL path.
+///
+/// This works like [`windows_to_wsl`](crate::windows_to_wsl), but operates on raw bytes (e.g.
+/// obtained from [`OsStr::as_encoded_bytes`]), so that file names which are not valid UTF-8 are
+/// preserved.
+///
+/// [`OsStr::as_encoded_bytes`]: https://doc.rust-lang.org/std/ffi/struct.OsStr.html#method.as_encoded_bytes
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`](crate::windows_to_wsl). Invalid
+/// UTF-8 sequences in the prefix reported by [`Error::InvalidPrefix`] are replaced by
+/// `U+FFFD REPLACEMENT CHARACTER`.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_bytes, Error};
+///
+/// assert_eq!(windows_to_wsl_bytes(b"C:\\Windows").unwrap(), b"/mnt/c/Windows");
+/// assert_eq!(windows_to_wsl_bytes(b"C:\\foo\\\xff\xfe.txt").unwrap(), b"/mnt/c/foo/\xff\xfe.txt");
+/// assert_eq!(windows_to_wsl_bytes(b"\\\\wsl$\\Ubuntu\\home\\\xff").unwrap(), b"/home/\xff");
+/// assert_eq!(windows_to_wsl_bytes(b"\\\\wsl.localhost\\Ubuntu/home/user").unwrap(), b"/home/user");
+/// assert_eq!(windows_to_wsl_bytes(b"\\\\?\\C:\\/mnt/foo").unwrap(), b"/mnt/c/mnt/foo");
+///
+/// assert_eq!(windows_to_wsl_bytes(b"foo\\\xff.txt").unwrap_err(), Error::RelativePath);
+/// assert_eq!(windows_to_wsl_bytes(b"\\\\?\\5:\\foo").unwrap_err(), Error::InvalidDriveLetter { got: "5".to_string() });
+/// assert_eq!(
+///     windows_to_wsl_bytes(b"\\\\server\\\xff\\foo.txt").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\server\\\u{FFFD}".to_string() },
+/// );
+/// ```
+pub fn windows_to_wsl_bytes(windows_path: &[u8]) -> Result<Vec<u8>, Error> {
+    windows_to_wsl_bytes_with(windows_path, &Options::default())
+}
+
+/// Convert a Windows path that is not necessarily valid UTF-8 to a WSL path using custom
+/// [`Options`].
+///
+/// See [`windows_to_wsl_bytes`] and [`windows_to_wsl_with`](crate::windows_to_wsl_with) for
+/// details.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl_with`](crate::windows_to_wsl_with).
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_bytes_with, Error, Options};
+///
+/// let options = Options::builder()
+///     .mount_root("/")
+///     .network_mount("/mnt/network")
+///     .current_distro("Ubuntu")
+///     .build();
+/// assert_eq!(windows_to_wsl_bytes_with(b"C:\\foo\\\xff", &options).unwrap(), b"/c/foo/\xff");
+/// assert_eq!(
+///     windows_to_wsl_bytes_with(b"\\\\server\\\xff\\foo", &options).unwrap(),
+///     b"/mnt/network/server/\xff/foo",
+/// );
+/// assert_eq!(windows_to_wsl_bytes_with(b"\\\\wsl$\\Ubuntu\\\xff", &options).unwrap(), b"/\xff");
+/// assert_eq!(
+///     windows_to_wsl_bytes_with(b"\\\\wsl$\\Debian\\\xff", &options).unwrap_err(),
+///     Error::ForeignDistro { distro: "Debian".to_string() },
+/// );
+/// ```
+pub fn windows_to_wsl_bytes_with(windows_path: &[u8], options: &Options) -> Result<Vec<u8>, Error> {
+    let mut output = Vec::new();
+    windows_to_wsl_buffer(windows_path, options, &mut output)?;
+    Ok(output)
+}
+
+/// Convert a WSL path that is not necessarily valid UTF-8 to a Windows path.
+///
+/// This works like [`wsl_to_windows`](crate::wsl_to_windows), but operates on raw bytes (e.g.
+/// obtained from [`OsStr::as_encoded_bytes`]), so that file names which are not valid UTF-8 are
+/// preserved.
+///
+/// [`OsStr::as_encoded_bytes`]: https://doc.rust-lang.org/std/ffi/struct.OsStr.html#method.as_encoded_bytes
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`](crate::wsl_to_windows). Invalid
+/// UTF-8 sequences in the prefix reported by [`Error::InvalidPrefix`] are replaced by
+/// `U+FFFD REPLACEMENT CHARACTER`.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_bytes, Error};
+///
+/// assert_eq!(wsl_to_windows_bytes(b"/mnt/c/Windows").unwrap(), b"C:\\Windows");
+/// assert_eq!(wsl_to_windows_bytes(b"/mnt/c/foo/\xff\xfe.txt").unwrap(), b"C:\\foo\\\xff\xfe.txt");
+///
+/// assert_eq!(wsl_to_windows_bytes(b"foo/\xff.txt").unwrap_err(), Error::RelativePath);
+/// assert_eq!(
+///     wsl_to_windows_bytes(b"/mnt/\xff\xfe/foo.txt").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt/\u{FFFD}\u{FFFD}".to_string() },
+/// );
+/// assert_eq!(
+///     wsl_to_windows_bytes(b"/mnt/5/foo.txt").unwrap_err(),
+///     Error::InvalidDriveLetter { got: "5".to_string() },
+/// );
+/// assert_eq!(
+///     wsl_to_windows_bytes(b"/mnt/c/\xff\\foo.txt").unwrap_err(),
+///     Error::InvalidWindowsComponent { component: "\u{FFFD}\\foo.txt".to_string(), ch: '\\' },
+/// );
+/// ```
+pub fn wsl_to_windows_bytes(wsl_path: &[u8]) -> Result<Vec<u8>, Error> {
+    wsl_to_windows_bytes_with(wsl_path, &Options::default())
+}
+
+/// Convert a WSL path that is not necessarily valid UTF-8 to a Windows path using custom
+/// [`Options`].
+///
+/// See [`wsl_to_windows_bytes`] and [`wsl_to_windows_with`](crate::wsl_to_windows_with) for
+/// details.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows_with`](crate::wsl_to_windows_with).
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_bytes_with, Error, Options, Separator};
+///
+/// let options = Options::builder()
+///     .mount_root("/windows")
+///     .separator(Separator::ForwardSlash)
+///     .validate_windows_names(true)
+///     .build();
+/// assert_eq!(wsl_to_windows_bytes_with(b"/windows/c/foo/\xff", &options).unwrap(), b"C:/foo/\xff");
+/// assert_eq!(
+///     wsl_to_windows_bytes_with(b"/windows/c/\xff:", &options).unwrap_err(),
+///     Error::InvalidWindowsComponent { component: "\u{FFFD}:".to_string(), ch: ':' },
+/// );
+/// assert_eq!(
+///     wsl_to_windows_bytes_with(b"/mnt/c/foo", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt".to_string() },
+/// );
+/// ```
+pub fn wsl_to_windows_bytes_with(wsl_path: &[u8], options: &Options) -> Result<Vec<u8>, Error> {
+    let mut output = Vec::new();
+    wsl_to_windows_buffer(wsl_path, options, &mut output)?;
+    Ok(output)
+}
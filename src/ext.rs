@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Extension trait to convert strings inline.
+
+use crate::{windows_to_wsl, wsl_to_windows, Error};
+use alloc::string::String;
+
+/// Extension methods to convert paths stored in strings.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::PathConvertExt;
+///
+/// assert_eq!("C:\\Windows".to_wsl_path().unwrap(), "/mnt/c/Windows");
+/// assert_eq!("/mnt/c/Windows".to_windows_path().unwrap(), "C:\\Windows");
+///
+/// let path = String::from("/mnt/d/foo");
+/// assert_eq!(path.to_windows_path().unwrap(), "D:\\foo");
+/// ```
+pub trait PathConvertExt {
+    /// Convert a Windows path to a WSL path (see [`windows_to_wsl`]).
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the same cases as [`windows_to_wsl`].
+    fn to_wsl_path(&self) -> Result<String, Error>;
+
+    /// Convert a WSL path to a Windows path (see [`wsl_to_windows`]).
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the same cases as [`wsl_to_windows`].
+    fn to_windows_path(&self) -> Result<String, Error>;
+}
+
+impl PathConvertExt for str {
+    fn to_wsl_path(&self) -> Result<String, Error> {
+        windows_to_wsl(self)
+    }
+
+    fn to_windows_path(&self) -> Result<String, Error> {
+        wsl_to_windows(self)
+    }
+}
+
+impl PathConvertExt for String {
+    fn to_wsl_path(&self) -> Result<String, Error> {
+        windows_to_wsl(self)
+    }
+
+    fn to_windows_path(&self) -> Result<String, Error> {
+        wsl_to_windows(self)
+    }
+}
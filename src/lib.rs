@@ -15,7 +15,15 @@
 //! Existing crates such as [`wslpath`](https://crates.io/crates/wslpath) call `wsl.exe wslpath`
 //! internally, which may lead to a lot of command invocations when multiple paths need to be
 //! converted.
+//!
+//! # Features
+//!
+//! The crate only requires [`alloc`] and can be used in `no_std` environments when the default
+//! `std` feature is disabled. The `std` feature adds the `std::error::Error` implementation for
+//! [`Error`] as well as helpers that work with native paths and files. The optional `serde` feature
+//! implements `Serialize` and `Deserialize` for [`Error`], [`Options`] and [`Conversion`].
 
+#![no_std]
 #![warn(unsafe_code)]
 #![warn(missing_docs)]
 #![cfg_attr(not(debug_assertions), deny(warnings))]
@@ -31,29 +39,267 @@
 #![cfg_attr(not(test), deny(clippy::panic_in_result_fn))]
 #![cfg_attr(not(debug_assertions), deny(clippy::used_underscore_binding))]
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod bytes;
+mod ext;
+mod mounts;
+mod names;
+#[cfg(feature = "std")]
+mod native;
+mod options;
+mod paths;
+mod quote;
+mod url;
+
+pub use bytes::{
+    windows_to_wsl_bytes, windows_to_wsl_bytes_with, wsl_to_windows_bytes,
+    wsl_to_windows_bytes_with,
+};
+pub use ext::PathConvertExt;
+pub use mounts::{
+    windows_to_wsl_with_table, windows_to_wsl_with_table_with, wsl_to_windows_with_table,
+    wsl_to_windows_with_table_with, MountTable,
+};
+#[cfg(all(feature = "std", windows))]
+pub use native::{windows_path_to_wsl, windows_to_wsl_canonicalized};
+#[cfg(all(feature = "std", unix))]
+pub use native::{wsl_path_to_windows, wsl_to_windows_canonicalized};
+pub use options::{
+    DriveCase, Options, OptionsBuilder, Separator, UnknownHostPolicy, VerbatimMode, WslVersion,
+};
+pub use paths::{WindowsPath, WslPath};
+pub use quote::{windows_to_wsl_shell_quoted, wsl_to_windows_ps_quoted};
+pub use url::{
+    file_url_to_windows, file_url_to_wsl, vscode_remote_to_wsl, windows_to_file_url,
+    wsl_to_vscode_remote,
+};
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use typed_path::{
+    Component, UnixComponent, UnixComponents, UnixPath, UnixPathBuf, Utf8Component,
     Utf8UnixComponent, Utf8UnixPath, Utf8UnixPathBuf, Utf8WindowsComponent, Utf8WindowsPath,
-    Utf8WindowsPathBuf, Utf8WindowsPrefix,
+    Utf8WindowsPathBuf, Utf8WindowsPrefix, WindowsComponent, WindowsComponents, WindowsPrefix,
+    WindowsPrefixComponent,
 };
 
 /// Represents an error that occurred during conversion.
-#[derive(Debug, PartialEq)]
+///
+/// The [`Display`](core::fmt::Display) implementation produces a short, human-readable message:
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl, Error};
+///
+/// assert_eq!(Error::RelativePath.to_string(), "input path is relative and cannot be converted");
+/// assert_eq!(
+///     Error::InvalidPrefix { prefix: "/etc".to_string() }.to_string(),
+///     "input path prefix `/etc` is invalid",
+/// );
+///
+/// // With the `std` feature, errors can be propagated with `?` as boxed trait objects
+/// # #[cfg(feature = "std")]
+/// # {
+/// fn convert() -> Result<String, Box<dyn std::error::Error>> {
+///     Ok(windows_to_wsl("foo\\bar.txt")?)
+/// }
+/// assert_eq!(convert().unwrap_err().to_string(), "input path is relative and cannot be converted");
+/// # }
+/// ```
+///
+/// Errors can be cloned, compared and hashed, e.g. to aggregate the failure reasons of a batch:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use wslpath_rs::{windows_to_wsl_all, Error};
+///
+/// let failures: HashSet<Error> = windows_to_wsl_all(["foo", "C:foo", "bar", "C:\\Windows"])
+///     .into_iter()
+///     .filter_map(Result::err)
+///     .collect();
+/// assert_eq!(failures.len(), 2);
+/// assert!(failures.contains(&Error::RelativePath));
+/// assert!(failures.contains(&Error::DriveRelativePath { drive: 'C' }));
+/// ```
+///
+/// With the `serde` feature, errors are serialized with their variant name in a `kind` field:
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use wslpath_rs::Error;
+///
+/// let error = Error::InvalidPrefix { prefix: "/etc".to_string() };
+/// let json = serde_json::to_string(&error).unwrap();
+/// assert_eq!(json, r#"{"kind":"InvalidPrefix","prefix":"/etc"}"#);
+/// assert_eq!(serde_json::from_str::<Error>(&json).unwrap(), error);
+/// assert_eq!(serde_json::to_string(&Error::RelativePath).unwrap(), r#"{"kind":"RelativePath"}"#);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+#[non_exhaustive]
 pub enum Error {
     /// The input path is relative and thus cannot be converted.
     RelativePath,
     /// The input path prefix is invalid.
-    InvalidPrefix,
+    InvalidPrefix {
+        /// The rejected prefix, e.g. `\\?\UNC\server\share` or `/mnt/my_custom_mount`.
+        prefix: String,
+    },
+    /// The input path is relative to the current directory of a drive (e.g. `C:foo`).
+    DriveRelativePath {
+        /// The drive letter the path is relative to.
+        drive: char,
+    },
+    /// The input path refers to a Windows drive, but automatic mounting of drives is disabled.
+    AutomountDisabled,
+    /// The converted path is not valid UTF-8.
+    InvalidUtf8,
+    /// The input path is neither recognizable as a Windows path nor as a WSL path.
+    AmbiguousPath,
+    /// The input path leaves the drive or mount it started under (only in
+    /// [strict mode](Options::strict)).
+    EscapesRoot,
+    /// The input path refers to a drive, but the drive letter is not an ASCII letter (e.g.
+    /// `/mnt/5`).
+    InvalidDriveLetter {
+        /// The rejected drive letter.
+        got: String,
+    },
+    /// A component of the input path contains a character that is not allowed in Windows file
+    /// names (only if [`Options::validate_windows_names`] is enabled, except for backslashes,
+    /// which are always rejected).
+    InvalidWindowsComponent {
+        /// The rejected path component.
+        component: String,
+        /// The first character that is not allowed.
+        ch: char,
+    },
+    /// A component of the input path is a reserved device name on Windows, e.g. `CON` or
+    /// `nul.txt` (only if [`Options::validate_windows_names`] is enabled).
+    ReservedWindowsName {
+        /// The rejected path component.
+        component: String,
+    },
+    /// A component of the input path ends with a dot or space, which Windows strips silently, so
+    /// that the path would refer to a different file (only if
+    /// [`Options::validate_windows_names`] is enabled).
+    AmbiguousWindowsName {
+        /// The rejected path component.
+        component: String,
+    },
+    /// The input path refers to a device that is not a drive (e.g. `\\.\PhysicalDrive0`).
+    DevicePath {
+        /// The name of the device, e.g. `PhysicalDrive0`.
+        device: String,
+    },
+    /// The input path is located on a filesystem that WSL mounts for internal use below the mount
+    /// root (e.g. `/mnt/wsl` or `/mnt/wslg`), which does not correspond to a Windows drive.
+    WslInternalMount {
+        /// The mount point of the internal filesystem, e.g. `/mnt/wsl`.
+        mount: String,
+    },
+    /// The input URI is malformed or uses an unsupported scheme.
+    InvalidUri {
+        /// The rejected URI.
+        uri: String,
+    },
+    /// The input path is empty or consists only of whitespace (e.g. because an environment
+    /// variable that was expected to contain a path is not set).
+    EmptyPath,
+    /// The input path starts with the home directory of another user (e.g. `~user/foo`), which
+    /// cannot be resolved without access to the user database.
+    UnknownUserHome {
+        /// The name of the user, e.g. `user` for `~user/foo`.
+        user: String,
+    },
+    /// The input path is located on the filesystem of a WSL distribution other than
+    /// [`Options::current_distro`], which is not accessible from inside WSL.
+    ForeignDistro {
+        /// The name of the other distribution, e.g. `Debian`.
+        distro: String,
+    },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::RelativePath => write!(f, "input path is relative and cannot be converted"),
+            Self::InvalidPrefix { prefix } => write!(f, "input path prefix `{prefix}` is invalid"),
+            Self::DriveRelativePath { drive } => write!(
+                f,
+                "input path is relative to the current directory of drive `{drive}:` and cannot be converted"
+            ),
+            Self::AutomountDisabled => write!(f, "automatic mounting of Windows drives is disabled"),
+            Self::InvalidUtf8 => write!(f, "converted path is not valid UTF-8"),
+            Self::AmbiguousPath => write!(f, "input path is neither a Windows nor a WSL path"),
+            Self::EscapesRoot => write!(f, "input path escapes the root of its drive or mount"),
+            Self::InvalidDriveLetter { got } => write!(f, "`{got}` is not a valid drive letter"),
+            Self::InvalidWindowsComponent { component, ch } => write!(
+                f,
+                "path component `{component}` contains the character `{ch}`, which is not allowed on Windows"
+            ),
+            Self::ReservedWindowsName { component } => write!(
+                f,
+                "path component `{component}` is a reserved device name on Windows"
+            ),
+            Self::AmbiguousWindowsName { component } => write!(
+                f,
+                "path component `{component}` ends with a dot or space, which is stripped on Windows"
+            ),
+            Self::DevicePath { device } => write!(
+                f,
+                "input path refers to the device `{device}` and cannot be converted"
+            ),
+            Self::WslInternalMount { mount } => write!(
+                f,
+                "input path is located on the WSL internal mount `{mount}`, which is not a Windows drive"
+            ),
+            Self::InvalidUri { uri } => write!(f, "input URI `{uri}` is invalid"),
+            Self::EmptyPath => write!(f, "input path is empty"),
+            Self::UnknownUserHome { user } => write!(
+                f,
+                "cannot resolve the home directory of user `{user}`"
+            ),
+            Self::ForeignDistro { distro } => write!(
+                f,
+                "input path is located on the filesystem of the other WSL distribution `{distro}`"
+            ),
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 /// Convert a Windows path to a WSL path.
 ///
 /// The input path needs to be absolute. Path are normalized during conversion. UNC paths
-/// (`\\?\C:\...`) are supported.
+/// (`\\?\C:\...`) are supported. Both `\` and `/` are accepted as separators for non-UNC paths.
+///
+/// Paths to the filesystem of a WSL distribution (`\\wsl.localhost\<distro>\...` or the legacy
+/// `\\wsl$\<distro>\...`) are converted to paths relative to the distribution's root directory.
+///
+/// The root directory of a drive (e.g. `C:\` or `\\?\C:\`) is converted to the mount point of
+/// the drive without a trailing slash (e.g. `/mnt/c`). A drive without a root directory (e.g.
+/// `C:`) refers to the current directory of the drive instead and cannot be converted.
 ///
 /// # Errors
 ///
-/// If the path is not absolute, the method returns an [`Error::RelativePath`]. Paths not starting
-/// with a drive letter will lead to an [`Error::InvalidPrefix`].
+/// If the path is empty or consists only of whitespace, the method returns an
+/// [`Error::EmptyPath`]. If the path is not absolute, the method returns an
+/// [`Error::RelativePath`], or an [`Error::DriveRelativePath`] if the path is only relative to the
+/// current directory of a drive (e.g. `C:foo`). Paths not starting with a drive letter or a WSL
+/// host will lead to an
+/// [`Error::InvalidPrefix`], or to an [`Error::InvalidDriveLetter`] if the drive letter is not
+/// `A`-`Z`. Paths to devices other than drives (e.g. `\\.\PhysicalDrive0`) lead to an
+/// [`Error::DevicePath`].
 ///
 /// # Examples
 ///
@@ -65,111 +311,3363 @@ pub enum Error {
 /// assert_eq!(windows_to_wsl("D:\\foo\\..\\bar\\.\\baz.txt").unwrap(), "/mnt/d/bar/baz.txt");
 /// assert_eq!(windows_to_wsl("C:\\Program Files (x86)\\Foo\\bar.txt").unwrap(), "/mnt/c/Program Files (x86)/Foo/bar.txt");
 ///
+/// // The root directory of a drive is converted to the mount point of the drive
+/// assert_eq!(windows_to_wsl("C:\\").unwrap(), "/mnt/c");
+/// assert_eq!(windows_to_wsl("d:/").unwrap(), "/mnt/d");
+/// assert_eq!(windows_to_wsl("\\\\?\\C:\\").unwrap(), "/mnt/c");
+/// assert_eq!(windows_to_wsl("\\\\.\\C:\\").unwrap(), "/mnt/c");
+/// assert_eq!(windows_to_wsl("C:\\\\").unwrap(), "/mnt/c");
+/// assert_eq!(windows_to_wsl("C:\\.").unwrap(), "/mnt/c");
+///
+/// // Special characters and non-ASCII file names are preserved
+/// for name in ["a&b", "it's", "a+b", "a,b", "a;b", "a=b", "user@host", "[x]", "{y}", "café", "日本語"] {
+///     assert_eq!(windows_to_wsl(&format!("C:\\{name}\\{name}.txt")).unwrap(), format!("/mnt/c/{name}/{name}.txt"));
+/// }
+///
+/// // Forward slashes are accepted as separators, even when mixed with backslashes
+/// assert_eq!(windows_to_wsl("C:/Windows/System32").unwrap(), "/mnt/c/Windows/System32");
+/// assert_eq!(windows_to_wsl("C:/Windows\\System32").unwrap(), "/mnt/c/Windows/System32");
+/// assert_eq!(windows_to_wsl("D:\\foo/../bar\\./baz.txt").unwrap(), "/mnt/d/bar/baz.txt");
+///
+/// // Repeated separators are collapsed
+/// assert_eq!(windows_to_wsl("C:\\\\Windows\\\\\\\\System32").unwrap(), "/mnt/c/Windows/System32");
+/// assert_eq!(windows_to_wsl("C://Windows//").unwrap(), "/mnt/c/Windows");
+/// assert_eq!(windows_to_wsl("\\\\wsl$\\Ubuntu\\\\home\\\\\\user").unwrap(), "/home/user");
+///
 /// // UNC paths are supported
 /// assert_eq!(windows_to_wsl("\\\\?\\C:\\Windows").unwrap(), "/mnt/c/Windows");
 /// assert_eq!(windows_to_wsl("\\\\?\\D:\\foo\\..\\bar\\.\\baz.txt").unwrap(), "/mnt/d/bar/baz.txt");
 /// assert_eq!(windows_to_wsl("\\\\?\\C:\\Program Files (x86)\\Foo\\bar.txt").unwrap(), "/mnt/c/Program Files (x86)/Foo/bar.txt");
 ///
+/// // Forward slashes in verbatim paths become separators, but cannot leave the drive
+/// assert_eq!(windows_to_wsl("\\\\?\\C:\\/mnt/foo").unwrap(), "/mnt/c/mnt/foo");
+/// assert_eq!(windows_to_wsl("\\\\?\\C:\\a/..\\..\\foo").unwrap(), "/mnt/c/foo");
+///
+/// // Paths to the filesystem of a WSL distribution are supported
+/// assert_eq!(windows_to_wsl("\\\\wsl.localhost\\Ubuntu\\home\\user\\file").unwrap(), "/home/user/file");
+/// assert_eq!(windows_to_wsl("\\\\?\\UNC\\wsl.localhost\\Ubuntu\\home\\user\\file").unwrap(), "/home/user/file");
+/// assert_eq!(windows_to_wsl("\\\\wsl$\\Ubuntu\\home\\user\\file").unwrap(), "/home/user/file");
+/// assert_eq!(windows_to_wsl("\\\\?\\UNC\\wsl$\\distro\\home\\user\\file").unwrap(), "/home/user/file");
+/// assert_eq!(windows_to_wsl("\\\\?\\UNC\\WSL$\\distro\\home\\user\\file").unwrap(), "/home/user/file");
+/// assert_eq!(windows_to_wsl("\\\\wsl.localhost\\Ubuntu\\").unwrap(), "/");
+///
+/// // Separators can be mixed after the prefix (e.g. if a UNC path was joined with a WSL path)
+/// assert_eq!(windows_to_wsl("\\\\wsl.localhost\\Ubuntu/home/user").unwrap(), "/home/user");
+/// assert_eq!(windows_to_wsl("\\\\wsl$\\Ubuntu/home\\user/").unwrap(), "/home/user");
+/// assert_eq!(windows_to_wsl("//wsl.localhost/Ubuntu/home/user").unwrap(), "/home/user");
+/// assert_eq!(windows_to_wsl("\\\\wsl.localhost/Ubuntu\\home/user").unwrap(), "/home/user");
+///
+/// // Hostnames are case-insensitive
+/// assert_eq!(windows_to_wsl("\\\\?\\UNC\\WSL.LOCALHOST\\distro\\home\\user").unwrap(), "/home/user");
+/// assert_eq!(windows_to_wsl("\\\\?\\UNC\\Wsl.Localhost\\distro\\home\\user").unwrap(), "/home/user");
+/// assert_eq!(windows_to_wsl("\\\\WSL.localhost\\distro\\home\\user").unwrap(), "/home/user");
+///
+/// // Paths not starting with a drive letter or a WSL host are not supported
+/// assert_eq!(
+///     windows_to_wsl("\\\\?\\UNC\\other.domain\\share\\foo.txt").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\?\\UNC\\other.domain\\share".to_string() },
+/// );
+///
+/// // The distribution name cannot be separated from the hostname by repeated separators
+/// assert_eq!(
+///     windows_to_wsl("\\\\wsl$\\\\Ubuntu\\home").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\wsl$\\".to_string() },
+/// );
+///
+/// // Drives in the device namespace are supported
+/// assert_eq!(windows_to_wsl("\\\\.\\C:\\Windows").unwrap(), "/mnt/c/Windows");
+/// assert_eq!(windows_to_wsl("\\\\.\\d:\\foo\\..\\bar").unwrap(), "/mnt/d/bar");
+///
+/// // Other device paths are not supported
+/// assert_eq!(
+///     windows_to_wsl("\\\\.\\PhysicalDrive0\\foo").unwrap_err(),
+///     Error::DevicePath { device: "PhysicalDrive0".to_string() },
+/// );
+/// assert_eq!(
+///     windows_to_wsl("\\\\.\\PhysicalDrive0\\foo").unwrap_err().to_string(),
+///     "input path refers to the device `PhysicalDrive0` and cannot be converted",
+/// );
+///
+/// // Drive letters must be ASCII letters
+/// assert_eq!(windows_to_wsl("\\\\?\\5:\\foo").unwrap_err(), Error::InvalidDriveLetter { got: "5".to_string() });
+/// assert_eq!(windows_to_wsl("\\\\?\\ä:\\foo").unwrap_err(), Error::InvalidDriveLetter { got: "ä".to_string() });
+/// assert_eq!(windows_to_wsl("\\\\.\\5:\\foo").unwrap_err(), Error::InvalidDriveLetter { got: "5".to_string() });
+///
 /// // Relative paths are not supported
 /// assert_eq!(windows_to_wsl("Program Files (x86)\\Foo\\bar.txt").unwrap_err(), Error::RelativePath);
 /// assert_eq!(windows_to_wsl("..\\foo\\bar.txt").unwrap_err(), Error::RelativePath);
+///
+/// // Drive-relative paths are not supported either
+/// assert_eq!(windows_to_wsl("C:foo").unwrap_err(), Error::DriveRelativePath { drive: 'C' });
+/// assert_eq!(windows_to_wsl("C:").unwrap_err(), Error::DriveRelativePath { drive: 'C' });
+/// assert_eq!(windows_to_wsl("d:").unwrap_err(), Error::DriveRelativePath { drive: 'D' });
+/// assert_eq!(windows_to_wsl("C:.\\bar").unwrap_err(), Error::DriveRelativePath { drive: 'C' });
+///
+/// // Empty paths are rejected explicitly
+/// assert_eq!(windows_to_wsl("").unwrap_err(), Error::EmptyPath);
+/// assert_eq!(windows_to_wsl("   ").unwrap_err(), Error::EmptyPath);
+/// assert_eq!(windows_to_wsl("\t").unwrap_err(), Error::EmptyPath);
 /// ```
 pub fn windows_to_wsl(windows_path: &str) -> Result<String, Error> {
-    let path = Utf8WindowsPath::new(windows_path);
-    if !path.is_absolute() {
-        return Err(Error::RelativePath);
+    windows_to_wsl_with(windows_path, &Options::default())
+}
+
+/// Convert a Windows path to a WSL path using custom [`Options`].
+///
+/// This works like [`windows_to_wsl`], but drives are mounted below [`Options::mount_root`]
+/// instead of `/mnt`.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`]. If [`Options::automount_enabled`]
+/// is `false`, converting drive paths (including [administrative shares](Options::admin_share_hosts))
+/// fails with [`Error::AutomountDisabled`]. If
+/// [`Options::strict`] is `true`, paths that escape the root of their drive fail with
+/// [`Error::EscapesRoot`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{mount_point_for_drive, windows_to_wsl_with, DriveCase, Error, Options};
+///
+/// let mut options = Options::default();
+/// options.mount_root = "/windows".to_string();
+/// assert_eq!(windows_to_wsl_with("C:\\Windows", &options).unwrap(), "/windows/c/Windows");
+///
+/// // Trailing slashes in the mount root are ignored
+/// options.mount_root = "/windows/".to_string();
+/// assert_eq!(windows_to_wsl_with("C:\\Windows", &options).unwrap(), "/windows/c/Windows");
+///
+/// // Drives can be mounted directly below the root directory
+/// options.mount_root = "/".to_string();
+/// assert_eq!(windows_to_wsl_with("C:\\Windows", &options).unwrap(), "/c/Windows");
+///
+/// // Drive paths cannot be converted if drives are not mounted
+/// options.automount_enabled = false;
+/// assert_eq!(windows_to_wsl_with("C:\\Windows", &options).unwrap_err(), Error::AutomountDisabled);
+///
+/// // By default, `..` components at the drive root are ignored
+/// let mut options = Options::default();
+/// assert_eq!(windows_to_wsl_with("C:\\..\\..\\Windows", &options).unwrap(), "/mnt/c/Windows");
+///
+/// // In strict mode, this is an error
+/// options.strict = true;
+/// assert_eq!(windows_to_wsl_with("C:\\..\\..\\Windows", &options).unwrap_err(), Error::EscapesRoot);
+/// assert_eq!(windows_to_wsl_with("C:\\foo\\..\\..\\Windows", &options).unwrap_err(), Error::EscapesRoot);
+/// assert_eq!(windows_to_wsl_with("C:\\foo\\..\\Windows", &options).unwrap(), "/mnt/c/Windows");
+///
+/// // Normalization can be disabled to keep `..` components
+/// let mut options = Options::default();
+/// options.normalize = false;
+/// assert_eq!(windows_to_wsl_with("C:\\foo\\..\\bar", &options).unwrap(), "/mnt/c/foo/../bar");
+///
+/// // Trailing separators can be preserved
+/// let mut options = Options::default();
+/// assert_eq!(windows_to_wsl_with("C:\\Windows\\", &options).unwrap(), "/mnt/c/Windows");
+/// assert_eq!(windows_to_wsl_with("C:\\", &options).unwrap(), "/mnt/c");
+/// options.preserve_trailing_separator = true;
+/// assert_eq!(windows_to_wsl_with("C:\\Windows\\", &options).unwrap(), "/mnt/c/Windows/");
+/// assert_eq!(windows_to_wsl_with("C:/Windows/", &options).unwrap(), "/mnt/c/Windows/");
+/// assert_eq!(windows_to_wsl_with("C:\\", &options).unwrap(), "/mnt/c/");
+/// assert_eq!(windows_to_wsl_with("C:\\Windows", &options).unwrap(), "/mnt/c/Windows");
+///
+/// // The case of the drive letter can be changed or preserved
+/// let mut options = Options::default();
+/// options.drive_case = DriveCase::Upper;
+/// assert_eq!(windows_to_wsl_with("c:\\Windows", &options).unwrap(), "/mnt/C/Windows");
+/// options.drive_case = DriveCase::Preserve;
+/// assert_eq!(windows_to_wsl_with("D:\\foo", &options).unwrap(), "/mnt/D/foo");
+/// assert_eq!(windows_to_wsl_with("\\\\?\\d:\\foo", &options).unwrap(), "/mnt/d/foo");
+///
+/// // Administrative drive shares are only converted for the configured hosts
+/// let mut options = Options::default();
+/// assert_eq!(
+///     windows_to_wsl_with("\\\\localhost\\C$\\Windows", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\localhost\\C$".to_string() },
+/// );
+/// options.admin_share_hosts = vec!["localhost".to_string()];
+/// assert_eq!(windows_to_wsl_with("\\\\localhost\\C$\\Windows", &options).unwrap(), "/mnt/c/Windows");
+/// assert_eq!(windows_to_wsl_with("\\\\?\\UNC\\localhost\\C$\\Windows", &options).unwrap(), "/mnt/c/Windows");
+/// assert_eq!(windows_to_wsl_with("\\\\LOCALHOST\\d$\\", &options).unwrap(), "/mnt/d");
+/// assert_eq!(
+///     windows_to_wsl_with("\\\\server\\C$\\Windows", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\server\\C$".to_string() },
+/// );
+///
+/// // Network shares can be mapped to a mount point
+/// let mut options = Options::default();
+/// options.network_mount = Some("/mnt/network".to_string());
+/// assert_eq!(windows_to_wsl_with("\\\\server\\share\\dir", &options).unwrap(), "/mnt/network/server/share/dir");
+/// assert_eq!(windows_to_wsl_with("\\\\?\\UNC\\server\\share\\dir", &options).unwrap(), "/mnt/network/server/share/dir");
+/// assert_eq!(windows_to_wsl_with("\\\\wsl.localhost\\Ubuntu\\home", &options).unwrap(), "/home");
+///
+/// // Volumes without drive letter can be mapped to a mount point
+/// let mut options = Options::default();
+/// let volume = "\\\\?\\Volume{12345678-90ab-cdef-1234-567890abcdef}\\folder\\file";
+/// assert_eq!(
+///     windows_to_wsl_with(volume, &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\?\\Volume{12345678-90ab-cdef-1234-567890abcdef}".to_string() },
+/// );
+/// options.volume_mounts.insert("12345678-90AB-CDEF-1234-567890ABCDEF".to_string(), "/mnt/data".to_string());
+/// assert_eq!(windows_to_wsl_with(volume, &options).unwrap(), "/mnt/data/folder/file");
+///
+/// // The mount point of a drive can be used to map volumes to their drive letter
+/// let mount_point = mount_point_for_drive('D', &options).unwrap();
+/// options.volume_mounts.insert("{87654321-90ab-cdef-1234-567890abcdef}".to_string(), mount_point);
+/// assert_eq!(
+///     windows_to_wsl_with("\\\\?\\Volume{87654321-90ab-cdef-1234-567890abcdef}\\foo", &options).unwrap(),
+///     "/mnt/d/foo",
+/// );
+///
+/// // Every kind of prefix is handled, with and without the options that enable it
+/// let invalid_prefix = |prefix: &str| Err(Error::InvalidPrefix { prefix: prefix.to_string() });
+/// let cases = [
+///     // Disk
+///     ("C:\\foo", Ok("/mnt/c/foo"), Ok("/mnt/c/foo")),
+///     // VerbatimDisk
+///     ("\\\\?\\C:\\foo", Ok("/mnt/c/foo"), Ok("/mnt/c/foo")),
+///     // DeviceNS
+///     ("\\\\.\\C:\\foo", Ok("/mnt/c/foo"), Ok("/mnt/c/foo")),
+///     ("\\\\.\\PhysicalDrive0\\foo", Err(Error::DevicePath { device: "PhysicalDrive0".to_string() }), Err(Error::DevicePath { device: "PhysicalDrive0".to_string() })),
+///     // UNC
+///     ("\\\\wsl$\\Ubuntu\\foo", Ok("/foo"), Ok("/foo")),
+///     ("\\\\localhost\\C$\\foo", invalid_prefix("\\\\localhost\\C$"), Ok("/mnt/c/foo")),
+///     ("\\\\server\\share\\foo", invalid_prefix("\\\\server\\share"), Ok("/mnt/network/server/share/foo")),
+///     // VerbatimUNC
+///     ("\\\\?\\UNC\\wsl$\\Ubuntu\\foo", Ok("/foo"), Ok("/foo")),
+///     ("\\\\?\\UNC\\localhost\\C$\\foo", invalid_prefix("\\\\?\\UNC\\localhost\\C$"), Ok("/mnt/c/foo")),
+///     ("\\\\?\\UNC\\server\\share\\foo", invalid_prefix("\\\\?\\UNC\\server\\share"), Ok("/mnt/network/server/share/foo")),
+///     // Verbatim
+///     ("\\\\?\\Volume{1}\\foo", invalid_prefix("\\\\?\\Volume{1}"), Ok("/mnt/data/foo")),
+///     ("\\\\?\\GLOBALROOT\\Device\\foo", invalid_prefix("\\\\?\\GLOBALROOT"), invalid_prefix("\\\\?\\GLOBALROOT")),
+///     ("\\\\?\\server\\share\\foo", invalid_prefix("\\\\?\\server"), invalid_prefix("\\\\?\\server")),
+/// ];
+/// let options = Options::builder()
+///     .admin_share_host("localhost")
+///     .network_mount("/mnt/network")
+///     .volume_mount("{1}", "/mnt/data")
+///     .build();
+/// for (path, default, configured) in cases {
+///     assert_eq!(windows_to_wsl_with(path, &Options::default()).as_deref(), default.as_deref(), "{path}");
+///     assert_eq!(windows_to_wsl_with(path, &options).as_deref(), configured.as_deref(), "{path}");
+/// }
+/// ```
+pub fn windows_to_wsl_with(windows_path: &str, options: &Options) -> Result<String, Error> {
+    let mut output = String::new();
+    convert_into(&mut output, |buffer| {
+        windows_to_wsl_buffer(windows_path.as_bytes(), options, buffer)
+    })?;
+    Ok(output)
+}
+
+/// Convert a Windows path to a WSL path like [`windows_to_wsl_with`] does and write the result
+/// into `output`.
+///
+/// Besides the conversion itself, this handles [`Options::lenient_unicode`] and
+/// [`Options::on_unknown_host`]. On error, `output` is left empty.
+fn windows_to_wsl_buffer(
+    windows_path: &[u8],
+    options: &Options,
+    output: &mut Vec<u8>,
+) -> Result<(), Error> {
+    let replaced = replace_lookalikes(windows_path, options);
+    let path = typed_path::WindowsPath::new(&*replaced);
+    match convert_windows_path(path, options, output) {
+        Ok(_) => Ok(()),
+        Err(Error::InvalidPrefix { .. })
+            if options.on_unknown_host == UnknownHostPolicy::Passthrough
+                && is_network_share(path) =>
+        {
+            output.clear();
+            output.extend_from_slice(windows_path);
+            Ok(())
+        }
+        Err(error) => {
+            output.clear();
+            Err(error)
+        }
     }
+}
 
-    // "C:\foo" (6 chars) -> "/mnt/c/foo" (10 chars)
-    let expected_length = windows_path.len() + 4;
-    let mut output = Utf8UnixPathBuf::with_capacity(expected_length);
-    for component in path.components() {
-        match component {
-            Utf8WindowsComponent::Prefix(prefix_component) => match prefix_component.kind() {
-                Utf8WindowsPrefix::VerbatimDisk(disk) => {
-                    output.push("/mnt");
-                    output.push(disk.to_ascii_lowercase().to_string());
-                }
-                Utf8WindowsPrefix::Disk(disk) => {
-                    output.push("/mnt");
-                    output.push(disk.to_ascii_lowercase().to_string());
-                }
-                _ => {
-                    return Err(Error::InvalidPrefix);
-                }
-            },
-            Utf8WindowsComponent::RootDir => (),
-            Utf8WindowsComponent::CurDir => output.push("."),
-            Utf8WindowsComponent::Normal(name) => output.push(name),
-            Utf8WindowsComponent::ParentDir => output.push(".."),
-        };
+/// Returns `true` if the Windows path is located on a network share of a host that is not a WSL
+/// host (e.g. `\\server\share`).
+fn is_network_share(path: &typed_path::WindowsPath) -> bool {
+    let Some(WindowsComponent::Prefix(prefix_component)) = path.components().next() else {
+        return false;
+    };
+    matches!(
+        prefix_component.kind(),
+        WindowsPrefix::UNC(hostname, share) | WindowsPrefix::VerbatimUNC(hostname, share)
+            if !share.is_empty() && !is_wsl_hostname(hostname)
+    )
+}
+
+/// Run a conversion that writes into a byte buffer on the buffer of `out`, so that its
+/// capacity is reused.
+///
+/// The conversions only produce invalid UTF-8 for byte strings that are not valid UTF-8
+/// themselves, so [`Error::InvalidUtf8`] is not expected here. On error, `out` is left empty.
+fn convert_into(
+    out: &mut String,
+    convert: impl FnOnce(&mut Vec<u8>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut buffer = core::mem::take(out).into_bytes();
+    let result = convert(&mut buffer);
+    match (result, String::from_utf8(buffer)) {
+        (Ok(()), Ok(converted)) => {
+            *out = converted;
+            Ok(())
+        }
+        (result, converted) => {
+            let mut buffer = converted.map_or_else(|error| error.into_bytes(), String::into_bytes);
+            buffer.clear();
+            *out = String::from_utf8(buffer).unwrap_or_default();
+            result.and(Err(Error::InvalidUtf8))
+        }
     }
+}
 
-    Ok(output.normalize().into_string())
+/// Convert a Windows path to a WSL path and write the result into an existing `String`.
+///
+/// This works like [`windows_to_wsl`], but instead of allocating a new `String`, `out` is cleared
+/// and the converted path is written into it directly. This allows reusing the same buffer (and its
+/// capacity) when converting many paths in a row.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`]. On error, `out` is left empty.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_into, Error};
+///
+/// let mut out = String::with_capacity(256);
+/// windows_to_wsl_into("C:\\Windows", &mut out).unwrap();
+/// assert_eq!(out, "/mnt/c/Windows");
+///
+/// // The path is written into the existing buffer without reallocating it
+/// let buffer = out.as_ptr();
+/// windows_to_wsl_into("D:\\foo\\bar.txt", &mut out).unwrap();
+/// assert_eq!(out, "/mnt/d/foo/bar.txt");
+/// assert!(out.capacity() >= 256);
+/// assert_eq!(out.as_ptr(), buffer);
+///
+/// assert_eq!(windows_to_wsl_into("foo\\bar.txt", &mut out).unwrap_err(), Error::RelativePath);
+/// assert!(out.is_empty());
+/// ```
+pub fn windows_to_wsl_into(windows_path: &str, out: &mut String) -> Result<(), Error> {
+    convert_into(out, |buffer| {
+        windows_to_wsl_buffer(windows_path.as_bytes(), &Options::default(), buffer)
+    })
 }
 
-/// Convert a WSL path to a Windows path.
+/// Convert an already parsed Windows path to a WSL path.
 ///
-/// The input path needs to be absolute. Path are normalized during conversion.
+/// This works like [`windows_to_wsl`], but avoids parsing the path again when working with
+/// [`typed_path`] types.
 ///
 /// # Errors
 ///
-/// If the path is not absolute, the method returns an [`Error::RelativePath`]. Paths not starting
-/// with with `/mnt/<driveletter>` will lead to an [`Error::InvalidPrefix`].
+/// This function fails in the same cases as [`windows_to_wsl`].
 ///
 /// # Examples
 ///
 /// ```
-/// use wslpath_rs::{wsl_to_windows, Error};
+/// use typed_path::Utf8WindowsPath;
+/// use wslpath_rs::windows_to_wsl_from_path;
 ///
-/// // Absolute paths are supported
-/// assert_eq!(wsl_to_windows("/mnt/c/Windows").unwrap(), "C:\\Windows");
-/// assert_eq!(wsl_to_windows("/mnt/d/foo/../bar/./baz.txt").unwrap(), "D:\\bar\\baz.txt");
-/// assert_eq!(wsl_to_windows("/mnt/c/Program Files (x86)/Foo/bar.txt").unwrap(), "C:\\Program Files (x86)\\Foo\\bar.txt");
+/// let path = Utf8WindowsPath::new("C:\\Windows\\System32");
+/// assert_eq!(windows_to_wsl_from_path(path).unwrap(), "/mnt/c/Windows/System32");
+/// ```
+pub fn windows_to_wsl_from_path(windows_path: &Utf8WindowsPath) -> Result<String, Error> {
+    let mut output = String::new();
+    convert_into(&mut output, |buffer| {
+        let path = typed_path::WindowsPath::new(windows_path.as_str());
+        convert_windows_path(path, &Options::default(), buffer).map(drop)
+    })?;
+    Ok(output)
+}
+
+/// Convert a Windows path to a WSL path, borrowing the input if no conversion is necessary.
 ///
-/// // Absolute paths not starting with `/mnt/<driveletter>` are not supported
-/// assert_eq!(wsl_to_windows("/etc/fstab").unwrap_err(), Error::InvalidPrefix);
-/// assert_eq!(wsl_to_windows("/mnt/my_custom_mount/foo/bar.txt").unwrap_err(), Error::InvalidPrefix);
+/// This accepts and rejects exactly the same inputs as [`windows_to_wsl`]. The input is only
+/// borrowed if the converted path would be byte-identical to it. Since every absolute Windows path
+/// starts with a drive or UNC prefix that is replaced by a WSL root directory, this currently never
+/// happens and the result is always [`Cow::Owned`]. The return type leaves room to borrow in such
+/// cases without breaking callers.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`]. In particular, WSL paths such as
+/// `/mnt/c/Windows` are relative Windows paths and fail with an [`Error::RelativePath`].
+///
+/// # Examples
 ///
-/// // Relative paths are not supported
-/// assert_eq!(wsl_to_windows("Program Files (x86)/Foo/bar.txt").unwrap_err(), Error::RelativePath);
-/// assert_eq!(wsl_to_windows("../foo/bar.txt").unwrap_err(), Error::RelativePath);
 /// ```
-pub fn wsl_to_windows(wsl_path: &str) -> Result<String, Error> {
-    let path = Utf8UnixPath::new(wsl_path);
-    if !path.is_absolute() {
-        return Err(Error::RelativePath);
+/// use std::borrow::Cow;
+/// use wslpath_rs::{windows_to_wsl, windows_to_wsl_cow, Error};
+///
+/// let path = windows_to_wsl_cow("C:\\Windows").unwrap();
+/// assert!(matches!(path, Cow::Owned(_)));
+/// assert_eq!(path, "/mnt/c/Windows");
+///
+/// for path in ["/mnt/c/Windows", "/mnt/c/a\\b", "\\\\server\\share\\foo", "C:foo"] {
+///     assert_eq!(windows_to_wsl_cow(path).map(Cow::into_owned), windows_to_wsl(path));
+/// }
+/// assert_eq!(windows_to_wsl_cow("/mnt/c/Windows").unwrap_err(), Error::RelativePath);
+/// ```
+pub fn windows_to_wsl_cow(windows_path: &str) -> Result<Cow<'_, str>, Error> {
+    windows_to_wsl(windows_path).map(Cow::Owned)
+}
+
+/// Convert a Windows path to a WSL path and return it as a [`Utf8UnixPathBuf`].
+///
+/// This works like [`windows_to_wsl`], but returns a typed path instead of a `String`, which avoids
+/// parsing the result again when working with [`typed_path`] types. Its
+/// [components](Utf8UnixPath::components) can be transformed individually: the prefix of the
+/// Windows path is already resolved into them (e.g. `C:` results in the components `mnt` and
+/// `c`), and `.` and `..` components are resolved as well.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`].
+///
+/// # Examples
+///
+/// ```
+/// use typed_path::{Utf8Component, Utf8UnixComponent, Utf8UnixPath};
+/// use wslpath_rs::windows_to_wsl_path;
+///
+/// let path = windows_to_wsl_path("C:\\Windows\\System32").unwrap();
+/// assert_eq!(path, Utf8UnixPath::new("/mnt/c/Windows/System32"));
+/// assert_eq!(path.file_name(), Some("System32"));
+///
+/// let path = windows_to_wsl_path("C:\\a\\b").unwrap();
+/// let components: Vec<_> = path.components().collect();
+/// assert_eq!(
+///     components,
+///     [
+///         Utf8UnixComponent::RootDir,
+///         Utf8UnixComponent::Normal("mnt"),
+///         Utf8UnixComponent::Normal("c"),
+///         Utf8UnixComponent::Normal("a"),
+///         Utf8UnixComponent::Normal("b"),
+///     ],
+/// );
+///
+/// let path = windows_to_wsl_path("\\\\wsl$\\Ubuntu\\home\\.\\user\\..").unwrap();
+/// let components: Vec<_> = path.components().collect();
+/// assert_eq!(components, [Utf8UnixComponent::RootDir, Utf8UnixComponent::Normal("home")]);
+///
+/// // Prefixes that are not parsed as drives by `typed_path` are resolved as well
+/// let path = windows_to_wsl_path("\\\\.\\C:\\a").unwrap();
+/// let components: Vec<_> = path.components().map(|component| component.as_str()).collect();
+/// assert_eq!(components, ["/", "mnt", "c", "a"]);
+/// ```
+pub fn windows_to_wsl_path(windows_path: &str) -> Result<Utf8UnixPathBuf, Error> {
+    windows_to_wsl(windows_path).map(Utf8UnixPathBuf::from)
+}
+
+/// The kind of location that a converted path refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum PathKind {
+    /// A path on a Windows drive (e.g. `C:\Windows` or `/mnt/c/Windows`), including
+    /// [administrative shares](Options::admin_share_hosts) and drives in the device namespace.
+    Drive,
+    /// A path on the filesystem of a WSL distribution (e.g. `\\wsl.localhost\Ubuntu\home`).
+    WslLocalhost,
+    /// A path on a network share mounted below [`Options::network_mount`].
+    Network,
+    /// A path on a volume without drive letter mounted at one of the [`Options::volume_mounts`].
+    Volume,
+}
+
+/// The kind of prefix of a Windows path.
+///
+/// Besides distinguishing drive paths from UNC paths, this records the form of the prefix, i.e.
+/// whether it uses the `\\?\` prefix for verbatim paths or the `\\.\` prefix of the device
+/// namespace. Use [`wsl_to_windows_as`] to convert a WSL path back to the same form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum PrefixKind {
+    /// A drive (e.g. `C:\Windows`).
+    Disk,
+    /// A verbatim drive (e.g. `\\?\C:\Windows`).
+    VerbatimDisk,
+    /// A UNC path (e.g. `\\server\share` or `\\wsl.localhost\Ubuntu`).
+    Unc,
+    /// A verbatim UNC path (e.g. `\\?\UNC\server\share`).
+    VerbatimUnc,
+    /// A verbatim path that is neither a drive nor a UNC path (e.g. a volume like
+    /// `\\?\Volume{12345678-90ab-cdef-1234-567890abcdef}`).
+    Verbatim,
+    /// A path in the device namespace (e.g. `\\.\C:\Windows`).
+    Device,
+}
+
+impl PrefixKind {
+    /// Returns the kind of a parsed Windows prefix.
+    ///
+    /// `typed_path` does not parse drives in the device namespace or verbatim drives with other
+    /// characters than ASCII letters, so these are reported as [`PrefixKind::Device`] and
+    /// [`PrefixKind::Verbatim`].
+    fn of(prefix: &WindowsPrefix<'_>) -> Self {
+        match prefix {
+            WindowsPrefix::Disk(_) => Self::Disk,
+            WindowsPrefix::VerbatimDisk(_) => Self::VerbatimDisk,
+            WindowsPrefix::UNC(..) => Self::Unc,
+            WindowsPrefix::VerbatimUNC(..) => Self::VerbatimUnc,
+            WindowsPrefix::Verbatim(_) => Self::Verbatim,
+            WindowsPrefix::DeviceNS(_) => Self::Device,
+        }
+    }
+
+    /// Returns the kind of prefix of a Windows path created by this crate.
+    fn of_converted(path: &[u8]) -> Self {
+        if path.starts_with(br"\\?\UNC\") {
+            Self::VerbatimUnc
+        } else if path.starts_with(br"\\?\") {
+            Self::VerbatimDisk
+        } else if path.starts_with(br"\\") || path.starts_with(b"//") {
+            Self::Unc
+        } else {
+            Self::Disk
+        }
+    }
+}
+
+/// The result of a detailed path conversion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct Conversion {
+    /// The converted path.
+    pub path: String,
+    /// The kind of location the path refers to.
+    pub kind: PathKind,
+    /// The uppercase letter of the drive the path is located on, if it refers to a drive.
+    pub drive: Option<char>,
+    /// The name of the WSL distribution the path belongs to, if the input path referred to the
+    /// filesystem of a specific distribution (e.g. `\\wsl.localhost\<distro>\...`).
+    pub distro: Option<String>,
+    /// Whether the Windows side of the conversion is a UNC path (e.g. `\\server\share` or
+    /// `\\?\UNC\wsl$\Ubuntu`).
+    pub was_unc: bool,
+    /// The version of WSL the path was converted for (see [`Options::wsl_version`]).
+    pub wsl_version: Option<WslVersion>,
+    /// The kind of prefix of the Windows side of the conversion, i.e. of the input path when
+    /// converting to WSL and of the output path when converting to Windows.
+    pub prefix: PrefixKind,
+}
+
+/// Information about the location a path refers to, collected while converting it.
+#[derive(Debug)]
+struct Origin<'a> {
+    /// The kind of location the path refers to.
+    kind: PathKind,
+    /// The uppercase drive letter, if the path refers to a drive.
+    drive: Option<char>,
+    /// The name of the WSL distribution, if the path refers to a distribution's filesystem.
+    distro: Option<&'a [u8]>,
+    /// Whether the Windows path is a UNC path.
+    was_unc: bool,
+    /// The kind of prefix of the Windows path.
+    prefix: PrefixKind,
+}
+
+impl Origin<'_> {
+    /// Combine the origin with the converted path and the options it was converted with.
+    fn into_conversion(self, path: String, options: &Options) -> Conversion {
+        Conversion {
+            path,
+            kind: self.kind,
+            drive: self.drive,
+            distro: self
+                .distro
+                .map(|distro| String::from_utf8_lossy(distro).into_owned()),
+            was_unc: self.was_unc,
+            wsl_version: options.wsl_version,
+            prefix: self.prefix,
+        }
     }
+}
+
+/// Convert a Windows path to a WSL path and return additional information about the conversion.
+///
+/// This works like [`windows_to_wsl`], but also returns the kind of location the path refers to,
+/// the drive letter if the path is located on a drive, the name of the WSL distribution if the
+/// input path is located on a distribution's filesystem, and whether the input is a UNC path.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_detailed, PathKind, PrefixKind};
+///
+/// let conversion = windows_to_wsl_detailed("\\\\wsl.localhost\\Ubuntu\\home\\user").unwrap();
+/// assert_eq!(conversion.path, "/home/user");
+/// assert_eq!(conversion.kind, PathKind::WslLocalhost);
+/// assert_eq!(conversion.drive, None);
+/// assert_eq!(conversion.distro.as_deref(), Some("Ubuntu"));
+/// assert!(conversion.was_unc);
+/// assert_eq!(conversion.prefix, PrefixKind::Unc);
+///
+/// let conversion = windows_to_wsl_detailed("c:\\Windows").unwrap();
+/// assert_eq!(conversion.path, "/mnt/c/Windows");
+/// assert_eq!(conversion.kind, PathKind::Drive);
+/// assert_eq!(conversion.drive, Some('C'));
+/// assert_eq!(conversion.distro, None);
+/// assert!(!conversion.was_unc);
+/// assert_eq!(conversion.prefix, PrefixKind::Disk);
+///
+/// let conversion = windows_to_wsl_detailed("\\\\?\\C:\\Windows").unwrap();
+/// assert_eq!(conversion.prefix, PrefixKind::VerbatimDisk);
+/// ```
+pub fn windows_to_wsl_detailed(windows_path: &str) -> Result<Conversion, Error> {
+    windows_to_wsl_detailed_with(windows_path, &Options::default())
+}
+
+/// Convert a Windows path to a WSL path using custom [`Options`] and return additional information
+/// about the conversion.
+///
+/// See [`windows_to_wsl_detailed`] and [`windows_to_wsl_with`] for details.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl_with`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_detailed_with, Options, PathKind};
+///
+/// let mut options = Options::default();
+/// options.network_mount = Some("/mnt/network".to_string());
+/// options.admin_share_hosts.push("localhost".to_string());
+///
+/// let conversion = windows_to_wsl_detailed_with("\\\\server\\share\\foo", &options).unwrap();
+/// assert_eq!(conversion.path, "/mnt/network/server/share/foo");
+/// assert_eq!(conversion.kind, PathKind::Network);
+/// assert!(conversion.was_unc);
+///
+/// let conversion = windows_to_wsl_detailed_with("\\\\localhost\\d$\\foo", &options).unwrap();
+/// assert_eq!(conversion.path, "/mnt/d/foo");
+/// assert_eq!(conversion.kind, PathKind::Drive);
+/// assert_eq!(conversion.drive, Some('D'));
+/// assert!(conversion.was_unc);
+/// ```
+pub fn windows_to_wsl_detailed_with(
+    windows_path: &str,
+    options: &Options,
+) -> Result<Conversion, Error> {
+    let windows_path = replace_lookalikes(windows_path.as_bytes(), options);
+    let mut output = Vec::new();
+    let origin = convert_windows_path(
+        typed_path::WindowsPath::new(&*windows_path),
+        options,
+        &mut output,
+    )?;
+    let path = String::from_utf8(output).map_err(|_| Error::InvalidUtf8)?;
+    Ok(origin.into_conversion(path, options))
+}
+
+/// Convert a parsed Windows path to a normalized WSL path and write it into `output`.
+///
+/// Returns information about the location the path refers to.
+///
+/// The conversion operates on bytes, so that it is shared by the conversion of strings and of
+/// [byte strings](windows_to_wsl_bytes). The path is written into `output` directly, resolving `.`
+/// and `..` components in a single pass. Only unusual paths (e.g. with a [`Options::mount_root`]
+/// that is not normalized) are normalized using [`typed_path`] afterwards. On error, `output` may
+/// contain a partially converted path.
+fn convert_windows_path<'a>(
+    path: &'a typed_path::WindowsPath,
+    options: &Options,
+    output: &mut Vec<u8>,
+) -> Result<Origin<'a>, Error> {
+    check_windows_path_is_absolute(path)?;
 
+    output.clear();
     let mut components = path.components();
-    if components.next() != Some(Utf8UnixComponent::RootDir) {
-        return Err(Error::InvalidPrefix);
+    let Some(WindowsComponent::Prefix(prefix_component)) = components.next() else {
+        return Err(Error::RelativePath);
+    };
+    let rest_length = path.as_bytes().len() - prefix_component.as_bytes().len();
+    output.reserve(wsl_capacity_for_prefix(
+        &prefix_component.kind(),
+        rest_length,
+        options,
+    ));
+    let origin = push_wsl_prefix(output, prefix_component, options)?;
+    push_wsl_components(output, components, options)?;
+
+    if options.normalize && !is_normalized_unix_path(output) {
+        let normalized = UnixPath::new(&*output).normalize();
+        output.clear();
+        output.extend_from_slice(normalized.as_bytes());
     }
-    if components.next() != Some(Utf8UnixComponent::Normal("mnt")) {
-        return Err(Error::InvalidPrefix);
+
+    if options.preserve_trailing_separator
+        && matches!(path.as_bytes().last(), Some(b'\\' | b'/'))
+        && !output.ends_with(b"/")
+    {
+        output.push(b'/');
     }
 
-    // "/mnt/c/foo" (10 chars) -> "C:\foo" (6 chars)
-    let expected_length = wsl_path.len();
-    let mut output = Utf8WindowsPathBuf::with_capacity(expected_length);
-    if let Some(Utf8UnixComponent::Normal(drive)) = components.next() {
-        if drive.len() != 1 {
-            return Err(Error::InvalidPrefix);
+    debug_assert!(
+        has_wsl_root(output, &origin, options),
+        "{:?} does not start with the root of {origin:?}",
+        String::from_utf8_lossy(output),
+    );
+    Ok(origin)
+}
+
+/// Push the WSL equivalent of the prefix of a Windows path (e.g. `/mnt/c` for `C:`) to `output`.
+///
+/// Returns information about the location the prefix refers to.
+fn push_wsl_prefix<'a>(
+    output: &mut Vec<u8>,
+    prefix_component: WindowsPrefixComponent<'a>,
+    options: &Options,
+) -> Result<Origin<'a>, Error> {
+    let prefix = PrefixKind::of(&prefix_component.kind());
+    let mut origin = Origin {
+        kind: PathKind::Drive,
+        drive: None,
+        distro: None,
+        was_unc: matches!(prefix, PrefixKind::Unc | PrefixKind::VerbatimUnc),
+        prefix,
+    };
+    let disk = match prefix_component.kind() {
+        WindowsPrefix::VerbatimDisk(disk) | WindowsPrefix::Disk(disk) => {
+            // The drive letter returned by `typed_path` is always uppercase, so the original case
+            // has to be taken from the prefix itself (e.g. `d:`).
+            let bytes = prefix_component.as_bytes();
+            char::from(
+                bytes
+                    .len()
+                    .checked_sub(2)
+                    .map_or(disk, |index| bytes[index]),
+            )
+        }
+        // `typed_path` parses repeated separators after the hostname (e.g. `\\wsl$\\Ubuntu`) as a
+        // UNC prefix with an empty share name.
+        WindowsPrefix::UNC(_, b"") | WindowsPrefix::VerbatimUNC(_, b"") => {
+            return Err(Error::InvalidPrefix {
+                prefix: String::from_utf8_lossy(prefix_component.as_bytes()).into_owned(),
+            });
+        }
+        WindowsPrefix::UNC(hostname, share) | WindowsPrefix::VerbatimUNC(hostname, share)
+            if is_wsl_hostname(hostname) =>
+        {
+            if let Some(current_distro) = &options.current_distro {
+                if !share.eq_ignore_ascii_case(current_distro.as_bytes()) {
+                    return Err(Error::ForeignDistro {
+                        distro: String::from_utf8_lossy(share).into_owned(),
+                    });
+                }
+            }
+            output.push(b'/');
+            origin.kind = PathKind::WslLocalhost;
+            origin.distro = Some(share);
+            return Ok(origin);
+        }
+        WindowsPrefix::UNC(hostname, share) | WindowsPrefix::VerbatimUNC(hostname, share)
+            if is_admin_share(hostname, share, options) =>
+        {
+            char::from(share[0])
         }
+        WindowsPrefix::UNC(hostname, share) | WindowsPrefix::VerbatimUNC(hostname, share)
+            if options.network_mount.is_some() =>
+        {
+            output.push(b'/');
+            push_unix_path(
+                output,
+                options
+                    .network_mount
+                    .as_deref()
+                    .unwrap_or_default()
+                    .as_bytes(),
+            );
+            push_unix_path(output, hostname);
+            push_unix_path(output, share);
+            origin.kind = PathKind::Network;
+            return Ok(origin);
+        }
+        WindowsPrefix::Verbatim(name) | WindowsPrefix::DeviceNS(name)
+            if volume_mount_point(name, options).is_some() =>
+        {
+            push_unix_path(
+                output,
+                volume_mount_point(name, options)
+                    .unwrap_or_default()
+                    .as_bytes(),
+            );
+            origin.kind = PathKind::Volume;
+            return Ok(origin);
+        }
+        // `typed_path` only parses ASCII letters as verbatim drives and does not parse drives in
+        // the device namespace at all, so e.g. `\\?\5:\` or `\\.\C:\` end up as generic prefixes.
+        // Invalid drive letters are rejected when the mount point is pushed below.
+        WindowsPrefix::Verbatim(name) | WindowsPrefix::DeviceNS(name)
+            if unparsed_drive(name).is_some() =>
+        {
+            unparsed_drive(name).unwrap_or_default()
+        }
+        // Other devices (e.g. `\\.\PhysicalDrive0` or `\\.\COM1`) have no WSL equivalent.
+        WindowsPrefix::DeviceNS(device) => {
+            return Err(Error::DevicePath {
+                device: String::from_utf8_lossy(device).into_owned(),
+            });
+        }
+        // Network shares that are neither located on a WSL host, nor administrative shares, nor
+        // mounted below `Options::network_mount`, and verbatim paths that are neither drives nor
+        // mounted volumes (e.g. `\\?\GLOBALROOT\Device\...` or `\\?\server\share`, which lacks the
+        // `UNC\` of a verbatim network path).
+        WindowsPrefix::UNC(..) | WindowsPrefix::VerbatimUNC(..) | WindowsPrefix::Verbatim(_) => {
+            return Err(Error::InvalidPrefix {
+                prefix: String::from_utf8_lossy(prefix_component.as_bytes()).into_owned(),
+            });
+        }
+    };
 
-        output.push(format!("{}:\\", drive.to_ascii_uppercase()));
-    } else {
-        return Err(Error::InvalidPrefix);
+    push_mount_point(output, disk, options)?;
+    origin.drive = Some(disk.to_ascii_uppercase());
+    Ok(origin)
+}
+
+/// Returns the drive letter of a verbatim or device prefix that `typed_path` does not parse as a
+/// drive (e.g. `5` for `5:` in `\\?\5:\`, or `C` for `C:` in `\\.\C:\`).
+fn unparsed_drive(name: &[u8]) -> Option<char> {
+    let name = String::from_utf8_lossy(name);
+    let mut chars = name.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(drive), Some(':'), None) => Some(drive),
+        _ => None,
     }
+}
 
+/// Push the components following the prefix of a Windows path to the converted WSL path.
+///
+/// `..` components cannot remove what `output` already contains (i.e. the mount point of the
+/// drive or share).
+fn push_wsl_components(
+    output: &mut Vec<u8>,
+    components: WindowsComponents<'_>,
+    options: &Options,
+) -> Result<(), Error> {
+    let root_length = output.len();
+    let mut depth = 0usize;
     for component in components {
         match component {
-            Utf8UnixComponent::RootDir => (),
-            Utf8UnixComponent::CurDir => output.push("."),
-            Utf8UnixComponent::Normal(name) => output.push(name),
-            Utf8UnixComponent::ParentDir => output.push(".."),
+            WindowsComponent::Prefix(_) | WindowsComponent::RootDir => (),
+            WindowsComponent::CurDir => {
+                push_wsl_component(output, root_length, &mut depth, b".", options)?;
+            }
+            WindowsComponent::ParentDir => {
+                push_wsl_component(output, root_length, &mut depth, b"..", options)?;
+            }
+            // Forward slashes are not separators in verbatim paths (e.g. `\\?\C:\a/b`), but they
+            // are in WSL paths, so each part of the name is pushed separately.
+            WindowsComponent::Normal(name) => {
+                for name in name
+                    .split(|&byte| byte == b'/')
+                    .filter(|name| !name.is_empty())
+                {
+                    push_wsl_component(output, root_length, &mut depth, name, options)?;
+                }
+            }
         };
     }
+    Ok(())
+}
+
+/// Push a component of a Windows path to the converted WSL path, where `root_length` is the
+/// length of the mount point and `depth` the number of directories below it.
+///
+/// If [`Options::normalize`] is enabled, `.` components are skipped and `..` components remove
+/// the last directory.
+fn push_wsl_component(
+    output: &mut Vec<u8>,
+    root_length: usize,
+    depth: &mut usize,
+    name: &[u8],
+    options: &Options,
+) -> Result<(), Error> {
+    match name {
+        b"." if options.normalize => (),
+        // Like on Windows, `..` components at the root of a drive or share are ignored
+        b".." if *depth == 0 && options.normalize => {
+            leave_directory(*depth, options)?;
+        }
+        b".." if options.normalize => {
+            *depth = leave_directory(*depth, options)?;
+            let parent_length = output[root_length..]
+                .iter()
+                .rposition(|&byte| byte == b'/')
+                .map_or(root_length, |index| root_length + index);
+            output.truncate(parent_length);
+        }
+        b"." => push_unix_path(output, name),
+        b".." => {
+            *depth = leave_directory(*depth, options)?;
+            push_unix_path(output, name);
+        }
+        name => {
+            *depth += 1;
+            push_unix_path(output, name);
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if a WSL path converted from a Windows path starts with the mount point of its
+/// drive (e.g. `/mnt/c`), or with `/` for paths to the filesystem of a WSL distribution or a
+/// network share.
+///
+/// This is only used to check the conversion in debug builds.
+fn has_wsl_root(path: &[u8], origin: &Origin<'_>, options: &Options) -> bool {
+    match (origin.kind, origin.drive) {
+        // The drive letter is reported in uppercase, but may be lowercase in the mount point
+        (PathKind::Drive, Some(drive)) => {
+            [drive, drive.to_ascii_lowercase()]
+                .into_iter()
+                .any(|drive| {
+                    let mut mount_point = Vec::new();
+                    if push_mount_point(&mut mount_point, drive, options).is_err() {
+                        return false;
+                    }
+                    let starts_with = |mount_point: &[u8]| {
+                        path.strip_prefix(mount_point).is_some_and(|rest| {
+                            rest.is_empty() || rest.starts_with(b"/") || mount_point.ends_with(b"/")
+                        })
+                    };
+                    starts_with(&mount_point)
+                        || starts_with(UnixPath::new(&mount_point).normalize().as_bytes())
+                })
+        }
+        (PathKind::Drive, None) => false,
+        (PathKind::WslLocalhost | PathKind::Network, _) => path.starts_with(b"/"),
+        // Mount points of volumes are used as given
+        (PathKind::Volume, _) => true,
+    }
+}
+
+/// Returns the capacity to reserve for the WSL path converted from a Windows path with the given
+/// prefix, where `rest_length` is the length of the path after the prefix (e.g. `\foo`).
+///
+/// Unlike [`wsl_capacity_hint`], this takes the length of the prefix into account, which is
+/// removed completely for paths to the filesystem of a WSL distribution (e.g.
+/// `\\?\UNC\wsl.localhost\Ubuntu\foo` becomes `/foo`).
+fn wsl_capacity_for_prefix(
+    prefix: &WindowsPrefix<'_>,
+    rest_length: usize,
+    options: &Options,
+) -> usize {
+    let mount_point_length = match prefix {
+        WindowsPrefix::UNC(hostname, _) | WindowsPrefix::VerbatimUNC(hostname, _)
+            if is_wsl_hostname(hostname) =>
+        {
+            0
+        }
+        // "\\server\share" -> "/mnt/network/server/share"
+        WindowsPrefix::UNC(hostname, share) | WindowsPrefix::VerbatimUNC(hostname, share)
+            if options.network_mount.is_some() =>
+        {
+            options.network_mount.as_deref().unwrap_or_default().len()
+                + hostname.len()
+                + share.len()
+                + 3
+        }
+        // "C:" -> "/mnt/c" (with a leading separator for relative mount roots)
+        _ => options.mount_root.len() + 2 + usize::from(!options.mount_root.starts_with('/')),
+    };
+    mount_point_length + rest_length
+}
+
+/// Returns `true` if normalizing the WSL path would not change it, i.e. if it has no `.` or `..`
+/// components, no repeated separators and no trailing separator.
+fn is_normalized_unix_path(path: &[u8]) -> bool {
+    path == b"/"
+        || (!path.ends_with(b"/")
+            && path
+                .strip_prefix(b"/")
+                .unwrap_or(path)
+                .split(|&byte| byte == b'/')
+                .all(|name| !matches!(name, b"" | b"." | b"..")))
+}
+
+/// Characters that are replaced by their ASCII equivalent in
+/// [lenient Unicode mode](Options::lenient_unicode).
+const LOOKALIKES: [(&str, u8); 4] = [
+    ("\u{FF1A}", b':'),  // FULLWIDTH COLON
+    ("\u{FF0F}", b'/'),  // FULLWIDTH SOLIDUS
+    ("\u{FF3C}", b'\\'), // FULLWIDTH REVERSE SOLIDUS
+    ("\u{2215}", b'/'),  // DIVISION SLASH
+];
+
+/// Replace characters that look like separators or colons by their ASCII equivalents if
+/// [`Options::lenient_unicode`] is enabled.
+///
+/// Paths that are not valid UTF-8 are supported, only the lookalikes themselves are replaced.
+fn replace_lookalikes<'a>(path: &'a [u8], options: &Options) -> Cow<'a, [u8]> {
+    if !options.lenient_unicode {
+        return Cow::Borrowed(path);
+    }
+
+    let mut replaced = Vec::new();
+    let (mut start, mut index) = (0, 0);
+    while index < path.len() {
+        let lookalike = LOOKALIKES
+            .iter()
+            .find(|(lookalike, _)| path[index..].starts_with(lookalike.as_bytes()));
+        if let Some(&(lookalike, replacement)) = lookalike {
+            replaced.extend_from_slice(&path[start..index]);
+            replaced.push(replacement);
+            index += lookalike.len();
+            start = index;
+        } else {
+            index += 1;
+        }
+    }
+    if start == 0 {
+        return Cow::Borrowed(path);
+    }
+    replaced.extend_from_slice(&path[start..]);
+    Cow::Owned(replaced)
+}
+
+/// Returns the directory depth after handling a `..` component at the given depth below the root
+/// of the drive or mount.
+///
+/// In [strict mode](Options::strict), leaving the root fails with [`Error::EscapesRoot`].
+fn leave_directory(depth: usize, options: &Options) -> Result<usize, Error> {
+    match depth.checked_sub(1) {
+        Some(depth) => Ok(depth),
+        None if options.strict => Err(Error::EscapesRoot),
+        None => Ok(0),
+    }
+}
+
+/// Append a path to a WSL path like [`UnixPathBuf::push`](typed_path::UnixPathBuf::push) does,
+/// i.e. with a separator in between unless the appended path is empty or absolute (in which case
+/// it replaces the path).
+fn push_unix_path(output: &mut Vec<u8>, path: &[u8]) {
+    if path.is_empty() {
+        return;
+    }
 
-    Ok(output.normalize().into_string())
+    if path.starts_with(b"/") {
+        output.clear();
+    } else if !output.is_empty() && !output.ends_with(b"/") {
+        output.push(b'/');
+    }
+    output.extend_from_slice(path);
+}
+
+/// Push the mount point of a drive (e.g. `/mnt/c`) to a WSL path.
+fn push_mount_point(output: &mut Vec<u8>, drive: char, options: &Options) -> Result<(), Error> {
+    if !drive.is_ascii_alphabetic() {
+        return Err(Error::InvalidDriveLetter {
+            got: drive.to_string(),
+        });
+    }
+    if !options.automount_enabled {
+        return Err(Error::AutomountDisabled);
+    }
+
+    push_unix_path(output, b"/");
+    push_unix_path(output, options.mount_root.as_bytes());
+    push_unix_path(
+        output,
+        options
+            .drive_case
+            .apply(drive)
+            .encode_utf8(&mut [0; 4])
+            .as_bytes(),
+    );
+    Ok(())
+}
+
+/// Get the mount point of a volume from [`Options::volume_mounts`], given the name of a volume
+/// GUID prefix (e.g. `Volume{12345678-90ab-cdef-1234-567890abcdef}`).
+fn volume_mount_point<'a>(name: &[u8], options: &'a Options) -> Option<&'a str> {
+    let guid = name.strip_prefix(b"Volume{")?.strip_suffix(b"}")?;
+    options
+        .volume_mounts
+        .iter()
+        .find(|(volume, _)| {
+            volume
+                .trim_matches(['{', '}'])
+                .as_bytes()
+                .eq_ignore_ascii_case(guid)
+        })
+        .map(|(_, mount_point)| mount_point.as_str())
+}
+
+/// Returns `true` if the path is empty or consists only of whitespace.
+fn is_blank(path: &str) -> bool {
+    path.trim().is_empty()
+}
+
+/// Check that a Windows path is absolute, i.e. neither empty, relative nor drive-relative.
+fn check_windows_path_is_absolute(path: &typed_path::WindowsPath) -> Result<(), Error> {
+    if core::str::from_utf8(path.as_bytes()).is_ok_and(is_blank) {
+        return Err(Error::EmptyPath);
+    }
+    if path.is_absolute() {
+        return Ok(());
+    }
+
+    if let Some(WindowsComponent::Prefix(prefix_component)) = path.components().next() {
+        if let WindowsPrefix::Disk(drive) = prefix_component.kind() {
+            return Err(Error::DriveRelativePath {
+                drive: char::from(drive),
+            });
+        }
+    }
+    Err(Error::RelativePath)
+}
+
+/// Check that a WSL path is absolute, i.e. neither empty nor relative.
+fn check_wsl_path_is_absolute(path: &UnixPath) -> Result<(), Error> {
+    if core::str::from_utf8(path.as_bytes()).is_ok_and(is_blank) {
+        return Err(Error::EmptyPath);
+    }
+    if !path.is_absolute() {
+        return Err(Error::RelativePath);
+    }
+    Ok(())
+}
+
+/// Returns `true` if the given UNC share is an administrative drive share (e.g. `C$`) on one of
+/// the [`Options::admin_share_hosts`].
+fn is_admin_share(hostname: &[u8], share: &[u8], options: &Options) -> bool {
+    matches!(share, [letter, b'$'] if letter.is_ascii_alphabetic())
+        && options
+            .admin_share_hosts
+            .iter()
+            .any(|host| host.as_bytes().eq_ignore_ascii_case(hostname))
+}
+
+/// Returns `true` if the given UNC hostname refers to the filesystem of a WSL distribution.
+///
+/// Like all Windows hostnames, these are matched case-insensitively.
+fn is_wsl_hostname(hostname: impl AsRef<[u8]>) -> bool {
+    let hostname = hostname.as_ref();
+    hostname.eq_ignore_ascii_case(b"wsl.localhost") || hostname.eq_ignore_ascii_case(b"wsl$")
+}
+
+/// Returns `true` if the given directory below the mount root is a filesystem that WSL mounts for
+/// internal use.
+fn is_wsl_internal_mount(name: impl AsRef<[u8]>) -> bool {
+    matches!(name.as_ref(), b"wsl" | b"wslg")
+}
+
+/// Convert a WSL path to a Windows path.
+///
+/// The input path needs to be absolute. Path are normalized during conversion.
+///
+/// # Errors
+///
+/// If the path is empty or consists only of whitespace, the method returns an
+/// [`Error::EmptyPath`]. If the path is not absolute, the method returns an
+/// [`Error::RelativePath`]. Paths not starting with `/mnt/<driveletter>` will lead to an
+/// [`Error::InvalidPrefix`], or to an
+/// [`Error::InvalidDriveLetter`] if the drive letter is a single character other than `a`-`z`.
+/// Paths below the internal mounts `/mnt/wsl` and `/mnt/wslg` lead to an
+/// [`Error::WslInternalMount`]. File names containing a backslash cannot be represented on
+/// Windows and lead to an [`Error::InvalidWindowsComponent`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows, Error};
+///
+/// // Absolute paths are supported
+/// assert_eq!(wsl_to_windows("/mnt/c/Windows").unwrap(), "C:\\Windows");
+/// assert_eq!(wsl_to_windows("/mnt/d/foo/../bar/./baz.txt").unwrap(), "D:\\bar\\baz.txt");
+/// assert_eq!(wsl_to_windows("/mnt/c/Program Files (x86)/Foo/bar.txt").unwrap(), "C:\\Program Files (x86)\\Foo\\bar.txt");
+///
+/// // Special characters and non-ASCII file names are preserved
+/// for name in ["a&b", "it's", "a+b", "a,b", "a;b", "a=b", "user@host", "[x]", "{y}", "café", "日本語"] {
+///     assert_eq!(wsl_to_windows(&format!("/mnt/c/{name}/{name}.txt")).unwrap(), format!("C:\\{name}\\{name}.txt"));
+/// }
+///
+/// // The mount point of a drive is converted to the root of the drive
+/// assert_eq!(wsl_to_windows("/mnt/c").unwrap(), "C:\\");
+/// assert_eq!(wsl_to_windows("/mnt/c/").unwrap(), "C:\\");
+///
+/// // `..` components cannot leave the drive root (see `Options::strict` to reject such paths)
+/// assert_eq!(wsl_to_windows("/mnt/c/..").unwrap(), "C:\\");
+/// assert_eq!(wsl_to_windows("/mnt/c/../../..").unwrap(), "C:\\");
+/// assert_eq!(wsl_to_windows("/mnt/c/Windows/../../Users").unwrap(), "C:\\Users");
+///
+/// // Repeated separators are collapsed
+/// assert_eq!(wsl_to_windows("/mnt//c///Windows").unwrap(), "C:\\Windows");
+/// assert_eq!(wsl_to_windows("//mnt/c//foo//bar.txt").unwrap(), "C:\\foo\\bar.txt");
+///
+/// // Absolute paths not starting with `/mnt/<driveletter>` are not supported
+/// assert_eq!(
+///     wsl_to_windows("/etc/fstab").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/etc".to_string() },
+/// );
+/// assert_eq!(
+///     wsl_to_windows("/mnt/my_custom_mount/foo/bar.txt").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt/my_custom_mount".to_string() },
+/// );
+/// assert_eq!(wsl_to_windows("/mnt").unwrap_err(), Error::InvalidPrefix { prefix: "/mnt".to_string() });
+/// assert_eq!(wsl_to_windows("/mnt/").unwrap_err(), Error::InvalidPrefix { prefix: "/mnt".to_string() });
+///
+/// // Filesystems that WSL mounts for internal use are not Windows drives
+/// assert_eq!(wsl_to_windows("/mnt/wsl").unwrap_err(), Error::WslInternalMount { mount: "/mnt/wsl".to_string() });
+/// assert_eq!(
+///     wsl_to_windows("/mnt/wslg/foo").unwrap_err(),
+///     Error::WslInternalMount { mount: "/mnt/wslg".to_string() },
+/// );
+/// assert_eq!(wsl_to_windows("/mnt/w/foo").unwrap(), "W:\\foo");
+///
+/// // Single-character mount points must be ASCII letters
+/// assert_eq!(wsl_to_windows("/mnt/5/foo").unwrap_err(), Error::InvalidDriveLetter { got: "5".to_string() });
+/// assert_eq!(wsl_to_windows("/mnt/$/foo").unwrap_err(), Error::InvalidDriveLetter { got: "$".to_string() });
+/// assert_eq!(wsl_to_windows("/mnt/ä/foo").unwrap_err(), Error::InvalidDriveLetter { got: "ä".to_string() });
+///
+/// // Backslashes in file names would become separators, so they are rejected
+/// assert_eq!(
+///     wsl_to_windows("/mnt/c/weird\\name").unwrap_err(),
+///     Error::InvalidWindowsComponent { component: "weird\\name".to_string(), ch: '\\' },
+/// );
+///
+/// // Colons are only rejected if Windows names are validated (see `Options::validate_windows_names`)
+/// assert_eq!(wsl_to_windows("/mnt/c/weird:name").unwrap(), "C:\\weird:name");
+///
+/// // Relative paths are not supported
+/// assert_eq!(wsl_to_windows("Program Files (x86)/Foo/bar.txt").unwrap_err(), Error::RelativePath);
+/// assert_eq!(wsl_to_windows("../foo/bar.txt").unwrap_err(), Error::RelativePath);
+///
+/// // Empty paths are rejected explicitly
+/// assert_eq!(wsl_to_windows("").unwrap_err(), Error::EmptyPath);
+/// assert_eq!(wsl_to_windows("   ").unwrap_err(), Error::EmptyPath);
+/// assert_eq!(wsl_to_windows("\t").unwrap_err(), Error::EmptyPath);
+/// ```
+pub fn wsl_to_windows(wsl_path: &str) -> Result<String, Error> {
+    wsl_to_windows_with(wsl_path, &Options::default())
+}
+
+/// Convert a WSL path to a Windows path using custom [`Options`].
+///
+/// This works like [`wsl_to_windows`], but expects drives to be mounted below
+/// [`Options::mount_root`] instead of `/mnt`.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`]. Paths not starting with
+/// `<mount_root>/<driveletter>` will lead to an [`Error::InvalidPrefix`]. If
+/// [`Options::automount_enabled`] is `false`, converting drive paths fails with
+/// [`Error::AutomountDisabled`]. If [`Options::strict`] is `true`, paths that escape the mount
+/// point of their drive fail with [`Error::EscapesRoot`]. If [`Options::validate_windows_names`]
+/// is `true`, paths that cannot be represented on Windows fail with
+/// [`Error::InvalidWindowsComponent`], [`Error::ReservedWindowsName`] or
+/// [`Error::AmbiguousWindowsName`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_with, wsl_to_windows_with, DriveCase, Error, Options, Separator, VerbatimMode};
+///
+/// // The mount root may consist of multiple components
+/// let mut options = Options::default();
+/// options.mount_root = "/mnt/host".to_string();
+/// assert_eq!(wsl_to_windows_with("/mnt/host/c/Windows", &options).unwrap(), "C:\\Windows");
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/c/Windows", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt/c".to_string() },
+/// );
+///
+/// // Drives can be mounted directly below the root directory
+/// options.mount_root = "/".to_string();
+/// assert_eq!(wsl_to_windows_with("/c/Windows", &options).unwrap(), "C:\\Windows");
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/c/Windows", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt".to_string() },
+/// );
+///
+/// // Drive paths cannot be converted if drives are not mounted
+/// options.automount_enabled = false;
+/// assert_eq!(wsl_to_windows_with("/c/Windows", &options).unwrap_err(), Error::AutomountDisabled);
+///
+/// // By default, `..` components at the drive root are ignored
+/// let mut options = Options::default();
+/// assert_eq!(wsl_to_windows_with("/mnt/c/../../etc/passwd", &options).unwrap(), "C:\\etc\\passwd");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/..", &options).unwrap(), "C:\\");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/foo/../..", &options).unwrap(), "C:\\");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/foo/bar/../../../baz", &options).unwrap(), "C:\\baz");
+///
+/// // In strict mode, paths must not escape the drive root
+/// options.strict = true;
+/// assert_eq!(wsl_to_windows_with("/mnt/c/../../etc/passwd", &options).unwrap_err(), Error::EscapesRoot);
+/// assert_eq!(wsl_to_windows_with("/mnt/c/..", &options).unwrap_err(), Error::EscapesRoot);
+/// assert_eq!(wsl_to_windows_with("/mnt/c/./..", &options).unwrap_err(), Error::EscapesRoot);
+/// assert_eq!(wsl_to_windows_with("/mnt/c/foo/../..", &options).unwrap_err(), Error::EscapesRoot);
+/// assert_eq!(wsl_to_windows_with("/mnt/c/foo/bar/../../../baz", &options).unwrap_err(), Error::EscapesRoot);
+/// assert_eq!(wsl_to_windows_with("/mnt/c/foo/../bar", &options).unwrap(), "C:\\bar");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/foo/bar/../..", &options).unwrap(), "C:\\");
+///
+/// // The same applies to the root of network shares
+/// options.network_mount = Some("/mnt/network".to_string());
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/network/server/share/foo/..", &options).unwrap(),
+///     "\\\\server\\share\\",
+/// );
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/network/server/share/..", &options).unwrap_err(),
+///     Error::EscapesRoot,
+/// );
+///
+/// // Server names that would turn the path into a verbatim path are rejected
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/network/?/share/foo", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt/network/?".to_string() },
+/// );
+///
+/// // Normalization can be disabled to keep `..` components
+/// let mut options = Options::default();
+/// options.normalize = false;
+/// assert_eq!(wsl_to_windows_with("/mnt/c/foo/../bar", &options).unwrap(), "C:\\foo\\..\\bar");
+///
+/// // Trailing separators can be preserved
+/// let mut options = Options::default();
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows/", &options).unwrap(), "C:\\Windows");
+/// options.preserve_trailing_separator = true;
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows/", &options).unwrap(), "C:\\Windows\\");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows", &options).unwrap(), "C:\\Windows");
+///
+/// // Drive roots always end with a separator
+/// assert_eq!(wsl_to_windows_with("/mnt/c/", &options).unwrap(), "C:\\");
+/// assert_eq!(wsl_to_windows_with("/mnt/c", &options).unwrap(), "C:\\");
+///
+/// // The `\\?\` prefix for long paths can be added
+/// let mut options = Options::default();
+/// options.verbatim = VerbatimMode::Always;
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows", &options).unwrap(), "\\\\?\\C:\\Windows");
+///
+/// // Automatically add the prefix for paths that are too long for regular Windows APIs
+/// options.verbatim = VerbatimMode::Auto;
+/// let name = "a".repeat(256);
+/// let converted = wsl_to_windows_with(&format!("/mnt/c/{name}"), &options).unwrap();
+/// assert_eq!(converted.len(), 259);
+/// assert_eq!(converted, format!("C:\\{name}"));
+///
+/// let name = "a".repeat(257);
+/// let converted = wsl_to_windows_with(&format!("/mnt/c/{name}"), &options).unwrap();
+/// assert_eq!(converted, format!("\\\\?\\C:\\{name}"));
+///
+/// // Forward slashes can be used as separators
+/// let mut options = Options::default();
+/// options.separator = Separator::ForwardSlash;
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows/System32", &options).unwrap(), "C:/Windows/System32");
+/// assert_eq!(wsl_to_windows_with("/mnt/c", &options).unwrap(), "C:/");
+///
+/// // Verbatim paths always use backslashes
+/// options.verbatim = VerbatimMode::Always;
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows", &options).unwrap(), "\\\\?\\C:\\Windows");
+///
+/// // The case of the drive letter can be changed or preserved
+/// let mut options = Options::default();
+/// options.windows_drive_case = DriveCase::Lower;
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows", &options).unwrap(), "c:\\Windows");
+/// options.windows_drive_case = DriveCase::Preserve;
+/// assert_eq!(wsl_to_windows_with("/mnt/D/foo", &options).unwrap(), "D:\\foo");
+/// assert_eq!(wsl_to_windows_with("/mnt/d/foo", &options).unwrap(), "d:\\foo");
+///
+/// // Paths below the network mount point are converted to UNC paths
+/// let mut options = Options::default();
+/// options.network_mount = Some("/mnt/network".to_string());
+/// assert_eq!(wsl_to_windows_with("/mnt/network/server/share/dir", &options).unwrap(), "\\\\server\\share\\dir");
+/// assert_eq!(wsl_to_windows_with("/mnt/network/server/share", &options).unwrap(), "\\\\server\\share\\");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows", &options).unwrap(), "C:\\Windows");
+///
+/// // Network paths need both a server and a share
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/network/server", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt/network/server".to_string() },
+/// );
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/network", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt/network".to_string() },
+/// );
+///
+/// // SMB paths can be converted back and forth
+/// for windows_path in ["\\\\server\\share\\dir", "\\\\nas.local\\media\\Movies\\file.mkv"] {
+///     let wsl_path = windows_to_wsl_with(windows_path, &options).unwrap();
+///     assert_eq!(wsl_to_windows_with(&wsl_path, &options).unwrap(), windows_path);
+/// }
+///
+/// // File names that are not allowed on Windows can be rejected
+/// let mut options = Options::default();
+/// assert_eq!(wsl_to_windows_with("/mnt/c/a:b", &options).unwrap(), "C:\\a:b");
+/// options.validate_windows_names = true;
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/c/a:b", &options).unwrap_err(),
+///     Error::InvalidWindowsComponent { component: "a:b".to_string(), ch: ':' },
+/// );
+/// for ch in ['<', '>', '"', '|', '?', '*', '\t'] {
+///     let component = format!("foo{ch}bar");
+///     assert_eq!(
+///         wsl_to_windows_with(&format!("/mnt/c/dir/{component}/baz"), &options).unwrap_err(),
+///         Error::InvalidWindowsComponent { component, ch },
+///     );
+/// }
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Program Files (x86)", &options).unwrap(), "C:\\Program Files (x86)");
+///
+/// // Reserved device names are rejected too, regardless of case and extension
+/// for component in ["CON", "con.txt", "LPT3", "Nul.tar.gz", "aux .txt"] {
+///     assert_eq!(
+///         wsl_to_windows_with(&format!("/mnt/c/dir/{component}"), &options).unwrap_err(),
+///         Error::ReservedWindowsName { component: component.to_string() },
+///     );
+/// }
+/// assert_eq!(wsl_to_windows_with("/mnt/c/COM0", &options).unwrap(), "C:\\COM0");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/console.txt", &options).unwrap(), "C:\\console.txt");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/LPT10", &options).unwrap(), "C:\\LPT10");
+///
+/// // Windows strips trailing dots and spaces, so such names are rejected as well
+/// for component in ["foo.", "foo...", "bar "] {
+///     assert_eq!(
+///         wsl_to_windows_with(&format!("/mnt/c/{component}/baz"), &options).unwrap_err(),
+///         Error::AmbiguousWindowsName { component: component.to_string() },
+///     );
+/// }
+/// assert_eq!(wsl_to_windows_with("/mnt/c/foo.txt", &options).unwrap(), "C:\\foo.txt");
+/// assert_eq!(wsl_to_windows_with("/mnt/c/.config", &options).unwrap(), "C:\\.config");
+///
+/// // Uppercase drive mount points are accepted unless mount points are case-sensitive
+/// let mut options = Options::default();
+/// assert_eq!(wsl_to_windows_with("/mnt/C/Windows", &options).unwrap(), "C:\\Windows");
+/// options.case_sensitive_mount = true;
+/// assert_eq!(
+///     wsl_to_windows_with("/mnt/C/Windows", &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt/C".to_string() },
+/// );
+/// assert_eq!(wsl_to_windows_with("/mnt/c/Windows", &options).unwrap(), "C:\\Windows");
+/// ```
+pub fn wsl_to_windows_with(wsl_path: &str, options: &Options) -> Result<String, Error> {
+    let mut output = String::new();
+    convert_into(&mut output, |buffer| {
+        wsl_to_windows_buffer(wsl_path.as_bytes(), options, buffer)
+    })?;
+    Ok(output)
+}
+
+/// Convert a WSL path to a Windows path like [`wsl_to_windows_with`] does and write the result
+/// into `output`.
+///
+/// Besides the conversion itself, this handles [`Options::lenient_unicode`]. On error, `output` is
+/// left empty.
+fn wsl_to_windows_buffer(
+    wsl_path: &[u8],
+    options: &Options,
+    output: &mut Vec<u8>,
+) -> Result<(), Error> {
+    let wsl_path = replace_lookalikes(wsl_path, options);
+    let result = convert_wsl_path(UnixPath::new(&*wsl_path), options, output);
+    if result.is_err() {
+        output.clear();
+    }
+    result.map(drop)
+}
+
+/// Convert a WSL path to a Windows path and return information about the location it refers to.
+///
+/// This works like [`wsl_to_windows`], but also returns the kind of location the path refers to,
+/// the drive letter if the path is located on a drive, and whether the output is a UNC path.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_detailed, PathKind, PrefixKind};
+///
+/// let conversion = wsl_to_windows_detailed("/mnt/c/Windows").unwrap();
+/// assert_eq!(conversion.path, "C:\\Windows");
+/// assert_eq!(conversion.kind, PathKind::Drive);
+/// assert_eq!(conversion.drive, Some('C'));
+/// assert_eq!(conversion.distro, None);
+/// assert!(!conversion.was_unc);
+/// assert_eq!(conversion.prefix, PrefixKind::Disk);
+/// ```
+pub fn wsl_to_windows_detailed(wsl_path: &str) -> Result<Conversion, Error> {
+    wsl_to_windows_detailed_with(wsl_path, &Options::default())
+}
+
+/// Convert a WSL path to a Windows path using custom options and return information about the
+/// location it refers to.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows_with`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_detailed_with, Options, PathKind, WslVersion};
+///
+/// let mut options = Options::default();
+/// options.network_mount = Some("/mnt/network".to_string());
+///
+/// let conversion = wsl_to_windows_detailed_with("/mnt/network/server/share/foo", &options).unwrap();
+/// assert_eq!(conversion.path, "\\\\server\\share\\foo");
+/// assert_eq!(conversion.kind, PathKind::Network);
+/// assert_eq!(conversion.drive, None);
+/// assert!(conversion.was_unc);
+/// assert_eq!(conversion.wsl_version, None);
+///
+/// let options = Options::for_wsl_version(WslVersion::Wsl2);
+/// let conversion = wsl_to_windows_detailed_with("/mnt/c/Windows", &options).unwrap();
+/// assert_eq!(conversion.path, "C:\\Windows");
+/// assert_eq!(conversion.wsl_version, Some(WslVersion::Wsl2));
+/// ```
+pub fn wsl_to_windows_detailed_with(
+    wsl_path: &str,
+    options: &Options,
+) -> Result<Conversion, Error> {
+    let wsl_path = replace_lookalikes(wsl_path.as_bytes(), options);
+    let mut output = Vec::new();
+    let origin = convert_wsl_path(UnixPath::new(&*wsl_path), options, &mut output)?;
+    let path = String::from_utf8(output).map_err(|_| Error::InvalidUtf8)?;
+    Ok(origin.into_conversion(path, options))
+}
+
+/// Convert a WSL path to a Windows path with the given kind of prefix.
+///
+/// Together with [`Conversion::prefix`], this allows restoring the form of the original Windows
+/// path after a round trip: [`PrefixKind::VerbatimDisk`], [`PrefixKind::VerbatimUnc`] and
+/// [`PrefixKind::Verbatim`] produce a path with the `\\?\` prefix, [`PrefixKind::Device`] a path in
+/// the device namespace (`\\.\`), and [`PrefixKind::Disk`] and [`PrefixKind::Unc`] a path without
+/// any of these prefixes. Whether the result is a drive or a UNC path is determined by the WSL
+/// path.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_detailed, wsl_to_windows_as, PrefixKind};
+///
+/// for windows_path in ["C:\\Windows", "\\\\?\\C:\\Windows", "\\\\.\\C:\\Windows"] {
+///     let conversion = windows_to_wsl_detailed(windows_path).unwrap();
+///     assert_eq!(conversion.path, "/mnt/c/Windows");
+///     assert_eq!(wsl_to_windows_as(&conversion.path, conversion.prefix).unwrap(), windows_path);
+/// }
+///
+/// assert_eq!(wsl_to_windows_as("/mnt/c/Windows", PrefixKind::Disk).unwrap(), "C:\\Windows");
+/// assert_eq!(wsl_to_windows_as("/mnt/c/Windows", PrefixKind::VerbatimDisk).unwrap(), "\\\\?\\C:\\Windows");
+/// assert_eq!(wsl_to_windows_as("/mnt/c/Windows", PrefixKind::Device).unwrap(), "\\\\.\\C:\\Windows");
+/// ```
+pub fn wsl_to_windows_as(wsl_path: &str, prefix: PrefixKind) -> Result<String, Error> {
+    let verbatim = match prefix {
+        PrefixKind::Disk | PrefixKind::Unc | PrefixKind::Device => VerbatimMode::Never,
+        PrefixKind::VerbatimDisk | PrefixKind::VerbatimUnc | PrefixKind::Verbatim => {
+            VerbatimMode::Always
+        }
+    };
+    let options = Options {
+        verbatim,
+        ..Options::default()
+    };
+    let path = wsl_to_windows_with(wsl_path, &options)?;
+    if prefix != PrefixKind::Device {
+        return Ok(path);
+    }
+
+    // "C:\foo" -> "\\.\C:\foo", "\\server\share" -> "\\.\UNC\server\share"
+    Ok(match path.strip_prefix(r"\\") {
+        Some(unc_path) => format!(r"\\.\UNC\{unc_path}"),
+        None => format!(r"\\.\{path}"),
+    })
+}
+
+/// Convert an already parsed WSL path to a Windows path.
+///
+/// This works like [`wsl_to_windows`], but avoids parsing the path again when working with
+/// [`typed_path`] types.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`].
+///
+/// # Examples
+///
+/// ```
+/// use typed_path::Utf8UnixPath;
+/// use wslpath_rs::wsl_to_windows_from_path;
+///
+/// let path = Utf8UnixPath::new("/mnt/c/Windows/System32");
+/// assert_eq!(wsl_to_windows_from_path(path).unwrap(), "C:\\Windows\\System32");
+/// ```
+pub fn wsl_to_windows_from_path(wsl_path: &Utf8UnixPath) -> Result<String, Error> {
+    let mut output = String::new();
+    convert_into(&mut output, |buffer| {
+        let path = UnixPath::new(wsl_path.as_str());
+        convert_wsl_path(path, &Options::default(), buffer).map(drop)
+    })?;
+    Ok(output)
+}
+
+/// Convert a WSL path to a Windows path and return it as a [`Utf8WindowsPathBuf`].
+///
+/// This works like [`wsl_to_windows`], but returns a typed path instead of a `String`, which avoids
+/// parsing the result again when working with [`typed_path`] types.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`].
+///
+/// # Examples
+///
+/// ```
+/// use typed_path::Utf8WindowsPath;
+/// use wslpath_rs::wsl_to_windows_path;
+///
+/// let path = wsl_to_windows_path("/mnt/c/Windows").unwrap();
+/// assert_eq!(path, Utf8WindowsPath::new("C:\\Windows"));
+/// assert_eq!(path.join("System32"), Utf8WindowsPath::new("C:\\Windows\\System32"));
+/// ```
+pub fn wsl_to_windows_path(wsl_path: &str) -> Result<Utf8WindowsPathBuf, Error> {
+    wsl_to_windows(wsl_path).map(Utf8WindowsPathBuf::from)
+}
+
+/// Convert a parsed WSL path to a normalized Windows path and write it into `output`.
+///
+/// Returns information about the location the path refers to.
+///
+/// Like [`convert_windows_path`], this operates on bytes and may leave a partially converted path
+/// in `output` on error.
+fn convert_wsl_path(
+    path: &UnixPath,
+    options: &Options,
+    output: &mut Vec<u8>,
+) -> Result<Origin<'static>, Error> {
+    check_wsl_path_is_absolute(path)?;
+
+    output.clear();
+    let mut components = path.components();
+    if components.next() != Some(UnixComponent::RootDir) {
+        return Err(Error::InvalidPrefix {
+            prefix: String::new(),
+        });
+    }
+
+    output.reserve(windows_capacity_hint(path.as_bytes().len()));
+    let mut origin = Origin {
+        kind: PathKind::Drive,
+        drive: None,
+        distro: None,
+        was_unc: false,
+        prefix: PrefixKind::Disk,
+    };
+    let network_share = options
+        .network_mount
+        .as_deref()
+        .and_then(|network_mount| strip_mount_point(components.clone(), network_mount).ok());
+    if let Some((share_components, mut prefix)) = network_share {
+        // "/mnt/network/server/share" -> "\\server\share\"
+        components = share_components;
+        origin.kind = PathKind::Network;
+        origin.was_unc = true;
+        output.extend_from_slice(br"\\");
+        for _ in 0..2 {
+            match components.next() {
+                // `?` is neither a valid server nor share name, and `\\?\` would be parsed as the
+                // prefix of a verbatim path
+                Some(UnixComponent::Normal(name)) if name != b"?" => {
+                    names::check_windows_component(name, options)?;
+                    prefix.push(name);
+                    output.extend_from_slice(name);
+                    output.push(b'\\');
+                }
+                component => {
+                    if let Some(component) = component {
+                        prefix.push(component);
+                    }
+                    return Err(Error::InvalidPrefix {
+                        prefix: String::from_utf8_lossy(prefix.as_bytes()).into_owned(),
+                    });
+                }
+            }
+        }
+    } else {
+        let (drive_components, mut prefix) = strip_mount_point(components, &options.mount_root)
+            .map_err(|prefix| Error::InvalidPrefix {
+                prefix: String::from_utf8_lossy(prefix.as_bytes()).into_owned(),
+            })?;
+        components = drive_components;
+        match components.next() {
+            Some(UnixComponent::Normal(drive)) if is_drive_mount(drive, options) => {
+                let drive = parse_drive_letter(drive)?;
+                if !options.automount_enabled {
+                    return Err(Error::AutomountDisabled);
+                }
+                let drive_letter = options.windows_drive_case.apply(drive);
+                output.extend_from_slice(drive_letter.encode_utf8(&mut [0; 4]).as_bytes());
+                output.extend_from_slice(br":\");
+                origin.drive = Some(drive.to_ascii_uppercase());
+            }
+            Some(UnixComponent::Normal(mount)) if is_wsl_internal_mount(mount) => {
+                prefix.push(mount);
+                return Err(Error::WslInternalMount {
+                    mount: String::from_utf8_lossy(prefix.as_bytes()).into_owned(),
+                });
+            }
+            component => {
+                if let Some(component) = component {
+                    prefix.push(component);
+                }
+                return Err(Error::InvalidPrefix {
+                    prefix: String::from_utf8_lossy(prefix.as_bytes()).into_owned(),
+                });
+            }
+        }
+    }
+
+    push_windows_components(output, components, options)?;
+
+    if options.preserve_trailing_separator
+        && path.as_bytes().ends_with(b"/")
+        && !output.ends_with(b"\\")
+    {
+        output.push(b'\\');
+    }
+
+    format_windows_path(output, options);
+    debug_assert!(
+        has_windows_prefix(output),
+        "{:?} does not start with a drive or UNC prefix",
+        String::from_utf8_lossy(output),
+    );
+    origin.prefix = PrefixKind::of_converted(output);
+    Ok(origin)
+}
+
+/// Push the components following the mount point of a WSL path to the converted Windows path.
+///
+/// Windows paths resolve `..` components when pushing and mistake components containing a colon
+/// (e.g. `a:b`) for a drive prefix, so the path is assembled and normalized manually. `..`
+/// components cannot remove what `output` already contains (i.e. the drive or share).
+fn push_windows_components(
+    output: &mut Vec<u8>,
+    components: UnixComponents<'_>,
+    options: &Options,
+) -> Result<(), Error> {
+    let root_length = output.len();
+    let mut depth = 0usize;
+    for component in components {
+        let name: &[u8] = match component {
+            UnixComponent::RootDir => continue,
+            UnixComponent::CurDir if options.normalize => continue,
+            UnixComponent::CurDir => b".",
+            UnixComponent::Normal(name) => {
+                names::check_windows_component(name, options)?;
+                depth += 1;
+                name
+            }
+            UnixComponent::ParentDir => {
+                depth = leave_directory(depth, options)?;
+                if options.normalize {
+                    let parent_length = output[root_length..]
+                        .iter()
+                        .rposition(|&byte| byte == b'\\')
+                        .map_or(root_length, |index| root_length + index);
+                    output.truncate(parent_length);
+                    continue;
+                }
+                b".."
+            }
+        };
+        if !output.ends_with(b"\\") {
+            output.push(b'\\');
+        }
+        output.extend_from_slice(name);
+    }
+    Ok(())
+}
+
+/// Returns `true` if a Windows path converted from a WSL path starts with a drive prefix (e.g.
+/// `C:` or `\\?\C:`) or a UNC prefix (e.g. `\\server\share` or `\\?\UNC\server\share`).
+///
+/// This is only used to check the conversion in debug builds.
+fn has_windows_prefix(path: &[u8]) -> bool {
+    let mut components = typed_path::WindowsPath::new(path).components();
+    let Some(WindowsComponent::Prefix(prefix_component)) = components.next() else {
+        return false;
+    };
+    match prefix_component.kind() {
+        WindowsPrefix::Disk(_) | WindowsPrefix::VerbatimDisk(_) => {
+            components.next() == Some(WindowsComponent::RootDir)
+        }
+        WindowsPrefix::UNC(_, share) | WindowsPrefix::VerbatimUNC(_, share) => !share.is_empty(),
+        // Verbatim paths with forward slashes (e.g. `\\?\C:/foo`) are not split by `typed_path`
+        WindowsPrefix::Verbatim(name) => {
+            matches!(name, [drive, b':', ..] if drive.is_ascii_alphabetic())
+        }
+        WindowsPrefix::DeviceNS(_) => false,
+    }
+}
+
+/// Strip the components of a mount point (e.g. `/mnt`) from the start of a path's components.
+///
+/// Returns the remaining components together with the stripped prefix. If the path is not
+/// located below the mount point, the prefix up to the first mismatching component is returned as
+/// error.
+fn strip_mount_point<'a>(
+    mut components: UnixComponents<'a>,
+    mount_point: &str,
+) -> Result<(UnixComponents<'a>, UnixPathBuf), UnixPathBuf> {
+    let mut prefix = UnixPathBuf::from("/");
+    let mount_point_components = UnixPath::new(mount_point)
+        .components()
+        .filter(|component| component.is_normal());
+    for expected_component in mount_point_components {
+        let component = components.next();
+        if let Some(component) = component {
+            prefix.push(component);
+        }
+        if component != Some(expected_component) {
+            return Err(prefix);
+        }
+    }
+    Ok((components, prefix))
+}
+
+/// Apply the output formatting [`Options`] (i.e. [`Options::verbatim`] and
+/// [`Options::separator`]) to a converted Windows path.
+fn format_windows_path(path: &mut Vec<u8>, options: &Options) {
+    add_verbatim_prefix(path, options.verbatim);
+    // Forward slashes are not treated as separators in verbatim paths.
+    if options.separator == Separator::ForwardSlash && !path.starts_with(br"\\?\") {
+        for byte in path.iter_mut().filter(|byte| **byte == b'\\') {
+            *byte = b'/';
+        }
+    }
+}
+
+/// Returns `true` if the given directory below the mount root is the mount point of a drive, i.e.
+/// if it consists of a single character (that is lowercase in
+/// [case-sensitive mode](Options::case_sensitive_mount)).
+fn is_drive_mount(name: &[u8], options: &Options) -> bool {
+    core::str::from_utf8(name).is_ok_and(|name| name.chars().count() == 1)
+        && !(options.case_sensitive_mount && name.iter().any(u8::is_ascii_uppercase))
+}
+
+/// Parse the name of a single-character mount point (e.g. `c` in `/mnt/c`) as a drive letter.
+fn parse_drive_letter(drive: &[u8]) -> Result<char, Error> {
+    match drive {
+        [letter] if letter.is_ascii_alphabetic() => Ok(char::from(*letter)),
+        _ => Err(Error::InvalidDriveLetter {
+            got: String::from_utf8_lossy(drive).into_owned(),
+        }),
+    }
+}
+
+/// The maximum length of a Windows path (including the terminating null character) that can be
+/// used without the `\\?\` prefix.
+const MAX_PATH: usize = 260;
+
+/// Add the `\\?\` prefix to a Windows path (if it does not have one already) depending on the
+/// [`VerbatimMode`].
+fn add_verbatim_prefix(path: &mut Vec<u8>, verbatim: VerbatimMode) {
+    let use_prefix = match verbatim {
+        VerbatimMode::Never => false,
+        VerbatimMode::Always => true,
+        VerbatimMode::Auto => String::from_utf8_lossy(path).encode_utf16().count() >= MAX_PATH,
+    };
+    if !use_prefix || path.starts_with(br"\\?\") {
+        return;
+    }
+
+    // "\\server\share" -> "\\?\UNC\server\share", "C:\foo" -> "\\?\C:\foo"
+    if path.starts_with(br"\\") {
+        path.splice(..2, br"\\?\UNC\".iter().copied());
+    } else {
+        path.splice(..0, br"\\?\".iter().copied());
+    }
+}
+
+/// Convert a WSL path to a Windows path and write the result into an existing `String`.
+///
+/// This works like [`wsl_to_windows`], but instead of allocating a new `String`, `out` is cleared
+/// and the converted path is written into it directly. The capacity of `out` is only grown if it
+/// is smaller than [`windows_capacity_hint`] for the input. This allows reusing the same buffer (and its
+/// capacity) when converting many paths in a row.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`]. On error, `out` is left empty.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_into, Error};
+///
+/// let mut out = String::new();
+/// for (wsl_path, windows_path) in [("/mnt/c/Windows", "C:\\Windows"), ("/mnt/d/foo.txt", "D:\\foo.txt")] {
+///     wsl_to_windows_into(wsl_path, &mut out).unwrap();
+///     assert_eq!(out, windows_path);
+/// }
+///
+/// // Once it is large enough, the buffer is reused without reallocating it
+/// let buffer = out.as_ptr();
+/// wsl_to_windows_into("/mnt/e/bar", &mut out).unwrap();
+/// assert_eq!(out, "E:\\bar");
+/// assert_eq!(out.as_ptr(), buffer);
+///
+/// assert_eq!(wsl_to_windows_into("../foo/bar.txt", &mut out).unwrap_err(), Error::RelativePath);
+/// assert!(out.is_empty());
+/// ```
+pub fn wsl_to_windows_into(wsl_path: &str, out: &mut String) -> Result<(), Error> {
+    convert_into(out, |buffer| {
+        wsl_to_windows_buffer(wsl_path.as_bytes(), &Options::default(), buffer)
+    })
+}
+
+/// Returns the capacity to reserve for the WSL path converted from a Windows path of the given
+/// length (in bytes) with the default [`Options`].
+///
+/// Drive paths grow by 4 bytes (e.g. `C:\foo` becomes `/mnt/c/foo`), which is the largest
+/// growth for the default options. All other supported paths shrink during conversion (e.g.
+/// `\\?\C:\foo` or `\\wsl$\Ubuntu\foo`), so that the hint is an upper bound for them.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl, wsl_capacity_hint};
+///
+/// for path in ["C:\\foo", "C:\\", "\\\\?\\C:\\foo", "\\\\.\\C:\\foo", "\\\\wsl$\\Ubuntu\\foo"] {
+///     assert!(windows_to_wsl(path).unwrap().len() <= wsl_capacity_hint(path.len()));
+/// }
+/// assert_eq!(wsl_capacity_hint("C:\\foo".len()), "/mnt/c/foo".len());
+/// ```
+#[must_use]
+pub const fn wsl_capacity_hint(windows_len: usize) -> usize {
+    windows_len + 4
+}
+
+/// Returns the capacity to reserve for the Windows path converted from a WSL path of the given
+/// length (in bytes) with the default [`Options`].
+///
+/// Drive paths shrink by 3 or 4 bytes (e.g. `/mnt/c` becomes `C:\` and `/mnt/c/foo` becomes
+/// `C:\foo`), so the length of the WSL path is used as upper bound. Options that add a prefix
+/// (e.g. [`VerbatimMode::Always`]) may require one more reallocation.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_capacity_hint, wsl_to_windows};
+///
+/// for path in ["/mnt/c/foo", "/mnt/c", "/mnt/c/foo/", "/mnt/c/a:b"] {
+///     assert!(wsl_to_windows(path).unwrap().len() <= windows_capacity_hint(path.len()));
+/// }
+/// ```
+#[must_use]
+pub const fn windows_capacity_hint(wsl_len: usize) -> usize {
+    wsl_len
+}
+
+/// Convert a WSL path to a Windows path, falling back to a `\\wsl.localhost\<distro>\...` UNC path
+/// for paths that are not located on a Windows drive.
+///
+/// Paths below `/mnt/<driveletter>` are converted to drive paths like [`wsl_to_windows`] does.
+/// All other absolute paths are converted to UNC paths that refer to the filesystem of the WSL
+/// distribution named `distro`.
+///
+/// # Errors
+///
+/// If the path is not absolute, the method returns an [`Error::RelativePath`]. If the distribution
+/// name is empty or contains a path separator, the method returns an [`Error::InvalidPrefix`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_unc, Error};
+///
+/// // Paths outside of `/mnt/<driveletter>` are accessed via the distribution's filesystem
+/// assert_eq!(wsl_to_windows_unc("/home/user", "Ubuntu").unwrap(), "\\\\wsl.localhost\\Ubuntu\\home\\user");
+/// assert_eq!(wsl_to_windows_unc("/etc/fstab", "Ubuntu").unwrap(), "\\\\wsl.localhost\\Ubuntu\\etc\\fstab");
+/// assert_eq!(wsl_to_windows_unc("/home", "Ubuntu").unwrap(), "\\\\wsl.localhost\\Ubuntu\\home");
+/// assert_eq!(wsl_to_windows_unc("/home/", "Ubuntu").unwrap(), "\\\\wsl.localhost\\Ubuntu\\home");
+/// assert_eq!(
+///     wsl_to_windows_unc("/mnt/wsl/foo", "Ubuntu").unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\mnt\\wsl\\foo",
+/// );
+///
+/// // The root directory refers to the root of the distribution's filesystem, which always ends
+/// // with a separator (like `C:\`)
+/// assert_eq!(wsl_to_windows_unc("/", "Ubuntu").unwrap(), "\\\\wsl.localhost\\Ubuntu\\");
+/// assert_eq!(wsl_to_windows_unc("//", "Ubuntu").unwrap(), "\\\\wsl.localhost\\Ubuntu\\");
+/// assert_eq!(wsl_to_windows_unc("/..", "Ubuntu").unwrap(), "\\\\wsl.localhost\\Ubuntu\\");
+///
+/// // Paths on Windows drives are still converted to drive paths
+/// assert_eq!(wsl_to_windows_unc("/mnt/c/Windows", "Ubuntu").unwrap(), "C:\\Windows");
+///
+/// // The hostname is always lowercase, the distribution name is kept as given
+/// assert_eq!(
+///     wsl_to_windows_unc("//home//user/", "openSUSE-Leap").unwrap().as_bytes(),
+///     br"\\wsl.localhost\openSUSE-Leap\home\user",
+/// );
+///
+/// // Relative paths are not supported
+/// assert_eq!(wsl_to_windows_unc("../foo/bar.txt", "Ubuntu").unwrap_err(), Error::RelativePath);
+///
+/// // Distribution names must be a single path component
+/// assert_eq!(
+///     wsl_to_windows_unc("/home/user", "").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\wsl.localhost\\".to_string() },
+/// );
+/// assert_eq!(
+///     wsl_to_windows_unc("/home/user", "Ubuntu\\foo").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\wsl.localhost\\Ubuntu\\foo".to_string() },
+/// );
+/// ```
+pub fn wsl_to_windows_unc(wsl_path: &str, distro: &str) -> Result<String, Error> {
+    wsl_to_windows_unc_with(wsl_path, distro, &Options::default())
+}
+
+/// Convert a WSL path to a Windows path using custom [`Options`], falling back to a
+/// `\\wsl.localhost\<distro>\...` UNC path for paths that are not located on a Windows drive.
+///
+/// This works like [`wsl_to_windows_unc`], but expects drives to be mounted below
+/// [`Options::mount_root`] instead of `/mnt`. If [`Options::automount_enabled`] is `false`, all
+/// paths are converted to UNC paths.
+///
+/// # Errors
+///
+/// If the path is not absolute, the method returns an [`Error::RelativePath`]. If the distribution
+/// name is empty or contains a path separator, the method returns an [`Error::InvalidPrefix`]. In
+/// [strict mode](Options::strict), paths that leave the root of the distribution's filesystem fail
+/// with an [`Error::EscapesRoot`]. If [`Options::validate_windows_names`] is `true`, paths that cannot be represented on Windows
+/// fail with [`Error::InvalidWindowsComponent`], [`Error::ReservedWindowsName`] or
+/// [`Error::AmbiguousWindowsName`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_unc_with, Error, Options, Separator, VerbatimMode};
+///
+/// let mut options = Options::default();
+/// options.mount_root = "/windows".to_string();
+/// assert_eq!(wsl_to_windows_unc_with("/windows/c/Windows", "Ubuntu", &options).unwrap(), "C:\\Windows");
+/// assert_eq!(
+///     wsl_to_windows_unc_with("/mnt/c/Windows", "Ubuntu", &options).unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\mnt\\c\\Windows",
+/// );
+///
+/// options.verbatim = VerbatimMode::Always;
+/// assert_eq!(
+///     wsl_to_windows_unc_with("/home/user", "Ubuntu", &options).unwrap(),
+///     "\\\\?\\UNC\\wsl.localhost\\Ubuntu\\home\\user",
+/// );
+///
+/// options.verbatim = VerbatimMode::Never;
+/// options.separator = Separator::ForwardSlash;
+/// assert_eq!(
+///     wsl_to_windows_unc_with("/home/user", "Ubuntu", &options).unwrap(),
+///     "//wsl.localhost/Ubuntu/home/user",
+/// );
+///
+/// // Trailing separators can be preserved, the root of the distribution always ends with one
+/// let mut options = Options::default();
+/// options.preserve_trailing_separator = true;
+/// assert_eq!(
+///     wsl_to_windows_unc_with("/home/", "Ubuntu", &options).unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\home\\",
+/// );
+/// assert_eq!(
+///     wsl_to_windows_unc_with("/home", "Ubuntu", &options).unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\home",
+/// );
+/// assert_eq!(wsl_to_windows_unc_with("/", "Ubuntu", &options).unwrap(), "\\\\wsl.localhost\\Ubuntu\\");
+///
+/// // Without normalization, `..` components are kept
+/// let mut options = Options::default();
+/// options.normalize = false;
+/// assert_eq!(
+///     wsl_to_windows_unc_with("/home/user/../foo", "Ubuntu", &options).unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\home\\user\\..\\foo",
+/// );
+///
+/// // In strict mode, paths must not leave the root of the distribution's filesystem
+/// let mut options = Options::default();
+/// options.strict = true;
+/// assert_eq!(
+///     wsl_to_windows_unc_with("/home/../etc", "Ubuntu", &options).unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\etc",
+/// );
+/// assert_eq!(
+///     wsl_to_windows_unc_with("/home/../../b", "Ubuntu", &options).unwrap_err(),
+///     Error::EscapesRoot,
+/// );
+/// ```
+pub fn wsl_to_windows_unc_with(
+    wsl_path: &str,
+    distro: &str,
+    options: &Options,
+) -> Result<String, Error> {
+    match wsl_to_windows_with(wsl_path, options) {
+        Err(
+            Error::InvalidPrefix { .. }
+            | Error::InvalidDriveLetter { .. }
+            | Error::AutomountDisabled
+            | Error::WslInternalMount { .. },
+        ) => (),
+        result => return result,
+    }
+
+    distro_unc_path(wsl_path, distro, options)
+}
+
+/// Convert an absolute WSL path to a `\\wsl.localhost\<distro>\...` UNC path, regardless of
+/// whether it is located on a Windows drive.
+fn distro_unc_path(wsl_path: &str, distro: &str, options: &Options) -> Result<String, Error> {
+    // "/foo" (4 chars) -> "\\wsl.localhost\<distro>\foo" (20 chars + distro length)
+    let expected_length = wsl_path.len() + distro.len() + 16;
+    let mut output = String::with_capacity(expected_length);
+    output.push_str(r"\\wsl.localhost\");
+    output.push_str(distro);
+    if distro.is_empty() || distro.contains(['\\', '/']) {
+        return Err(Error::InvalidPrefix { prefix: output });
+    }
+    output.push('\\');
+    let mut output = output.into_bytes();
+    push_windows_components(&mut output, UnixPath::new(wsl_path).components(), options)?;
+
+    // The root of the distribution's filesystem already ends with a separator
+    if options.preserve_trailing_separator && wsl_path.ends_with('/') && !output.ends_with(b"\\") {
+        output.push(b'\\');
+    }
+
+    format_windows_path(&mut output, options);
+    String::from_utf8(output).map_err(|_| Error::InvalidUtf8)
+}
+
+/// Convert a WSL path that may start with `~` to a Windows path, using the given home directory.
+///
+/// A leading `~` component is replaced by `home` (e.g. `/home/user`) before the path is converted
+/// like [`wsl_to_windows_unc`] does, so that homes on the distribution's filesystem lead to a
+/// `\\wsl.localhost\<distro>\...` UNC path. A `~` anywhere else in the path is kept as is.
+///
+/// # Errors
+///
+/// If the path starts with the home directory of another user (e.g. `~user`), the method returns
+/// an [`Error::UnknownUserHome`]. Otherwise, it fails in the same cases as [`wsl_to_windows_unc`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_with_home, Error};
+///
+/// assert_eq!(
+///     wsl_to_windows_with_home("~", "/home/user", "Ubuntu").unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\home\\user",
+/// );
+/// assert_eq!(
+///     wsl_to_windows_with_home("~/projects", "/home/user", "Ubuntu").unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\home\\user\\projects",
+/// );
+///
+/// // Homes on a Windows drive are converted to drive paths
+/// assert_eq!(
+///     wsl_to_windows_with_home("~/projects", "/mnt/c/Users/user", "Ubuntu").unwrap(),
+///     "C:\\Users\\user\\projects",
+/// );
+///
+/// // Only a leading `~` refers to the home directory
+/// assert_eq!(
+///     wsl_to_windows_with_home("/tmp/~/foo", "/home/user", "Ubuntu").unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\tmp\\~\\foo",
+/// );
+/// assert_eq!(
+///     wsl_to_windows_with_home("~foo", "/home/user", "Ubuntu").unwrap_err(),
+///     Error::UnknownUserHome { user: "foo".to_string() },
+/// );
+/// assert_eq!(
+///     wsl_to_windows_with_home("~root/.bashrc", "/home/user", "Ubuntu").unwrap_err(),
+///     Error::UnknownUserHome { user: "root".to_string() },
+/// );
+/// ```
+pub fn wsl_to_windows_with_home(wsl_path: &str, home: &str, distro: &str) -> Result<String, Error> {
+    let Some(rest) = wsl_path.strip_prefix('~') else {
+        return wsl_to_windows_unc(wsl_path, distro);
+    };
+
+    if !rest.is_empty() && !rest.starts_with('/') {
+        let user = rest.find('/').map_or(rest, |index| &rest[..index]);
+        return Err(Error::UnknownUserHome {
+            user: user.to_string(),
+        });
+    }
+
+    let mut path = String::with_capacity(home.len() + rest.len());
+    path.push_str(home);
+    path.push_str(rest);
+    wsl_to_windows_unc(&path, distro)
+}
+
+/// The forms in which a Windows path can be referred to from WSL and from Windows, as returned by
+/// [`all_wsl_forms`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WslForms {
+    /// The WSL path, as returned by [`windows_to_wsl`] (e.g. `/mnt/c/Windows` or `/home/user`).
+    pub wsl_path: String,
+    /// The `\\wsl.localhost\<distro>\...` UNC path that refers to the same file through the
+    /// filesystem of the WSL distribution (e.g. `\\wsl.localhost\Ubuntu\mnt\c\Windows`).
+    pub unc_path: String,
+    /// The kind of location the path refers to. If it is [`PathKind::Drive`], the WSL path is
+    /// located below the mount point of the drive (e.g. `/mnt/c`), and the UNC path is usually
+    /// only useful if the drive is not accessible from Windows directly.
+    pub kind: PathKind,
+}
+
+/// Convert a Windows path to all forms in which it can be referred to from WSL.
+///
+/// This returns both the WSL path (like [`windows_to_wsl`]) and the UNC path through the
+/// filesystem of a WSL distribution (like [`wsl_to_windows_unc`], but also for paths located on a
+/// drive). If the input path is located on the filesystem of a distribution (e.g.
+/// `\\wsl$\Ubuntu\home`), the UNC path refers to that distribution, otherwise to `distro`.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`]. If `distro` is needed and either
+/// empty or contains a path separator, it fails with [`Error::InvalidPrefix`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{all_wsl_forms, PathKind};
+///
+/// let forms = all_wsl_forms("C:\\Windows", "Ubuntu").unwrap();
+/// assert_eq!(forms.wsl_path, "/mnt/c/Windows");
+/// assert_eq!(forms.unc_path, "\\\\wsl.localhost\\Ubuntu\\mnt\\c\\Windows");
+/// assert_eq!(forms.kind, PathKind::Drive);
+///
+/// let forms = all_wsl_forms("\\\\wsl$\\Debian\\home\\user", "Ubuntu").unwrap();
+/// assert_eq!(forms.wsl_path, "/home/user");
+/// assert_eq!(forms.unc_path, "\\\\wsl.localhost\\Debian\\home\\user");
+/// assert_eq!(forms.kind, PathKind::WslLocalhost);
+/// ```
+pub fn all_wsl_forms(windows_path: &str, distro: &str) -> Result<WslForms, Error> {
+    let options = Options::default();
+    let conversion = windows_to_wsl_detailed_with(windows_path, &options)?;
+    let unc_path = distro_unc_path(
+        &conversion.path,
+        conversion.distro.as_deref().unwrap_or(distro),
+        &options,
+    )?;
+    Ok(WslForms {
+        wsl_path: conversion.path,
+        unc_path,
+        kind: conversion.kind,
+    })
+}
+
+/// Convert a Windows path given relative to an absolute base directory to a WSL path.
+///
+/// The relative path is joined to the base directory before converting it with
+/// [`windows_to_wsl`], so that `..` components in the relative path refer to the parents of the
+/// base directory. If the relative path is absolute, it replaces the base directory.
+///
+/// # Errors
+///
+/// If the base directory is not absolute, the method returns an [`Error::RelativePath`] (or an
+/// [`Error::DriveRelativePath`] if it is only relative to the current directory of a drive). A
+/// relative path that is relative to the root or a drive (e.g. `\foo` or `C:foo`) leads to an
+/// [`Error::InvalidPrefix`]. Otherwise, it fails in the same cases as [`windows_to_wsl`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_relative, Error};
+///
+/// assert_eq!(windows_to_wsl_relative("C:\\Users\\user", "Documents\\notes.txt").unwrap(), "/mnt/c/Users/user/Documents/notes.txt");
+/// assert_eq!(windows_to_wsl_relative("C:\\Users\\user\\", "../other/./file.txt").unwrap(), "/mnt/c/Users/other/file.txt");
+/// assert_eq!(windows_to_wsl_relative("C:\\Users\\user", "").unwrap(), "/mnt/c/Users/user");
+/// assert_eq!(windows_to_wsl_relative("C:\\Users\\user", "D:\\foo").unwrap(), "/mnt/d/foo");
+///
+/// assert_eq!(windows_to_wsl_relative("Users\\user", "Documents").unwrap_err(), Error::RelativePath);
+/// assert_eq!(
+///     windows_to_wsl_relative("C:\\Users\\user", "\\Windows").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\".to_string() },
+/// );
+/// ```
+pub fn windows_to_wsl_relative(base: &str, relative: &str) -> Result<String, Error> {
+    check_windows_path_is_absolute(typed_path::WindowsPath::new(base))?;
+
+    let relative_path = Utf8WindowsPath::new(relative);
+    if relative_path.is_absolute() {
+        return windows_to_wsl(relative);
+    }
+    if let Some(component @ (Utf8WindowsComponent::Prefix(_) | Utf8WindowsComponent::RootDir)) =
+        relative_path.components().next()
+    {
+        return Err(Error::InvalidPrefix {
+            prefix: component.as_str().to_string(),
+        });
+    }
+
+    // Joining the paths with `Utf8WindowsPath::join` would mistake components containing a colon
+    // (e.g. `a:b`) for a drive prefix.
+    let mut path = String::with_capacity(base.len() + relative.len() + 1);
+    path.push_str(base);
+    if !base.ends_with(['\\', '/']) {
+        path.push('\\');
+    }
+    path.push_str(relative);
+    windows_to_wsl(&path)
+}
+
+/// Compute the relative WSL path from one Windows path to another.
+///
+/// Both paths are converted with [`windows_to_wsl_detailed`] first. Like on Windows, path
+/// components on drives are compared case-insensitively when looking for the common ancestor
+/// directory. Paths on the filesystem of a WSL distribution are case-sensitive, so their
+/// components are compared exactly.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`]. If the paths are located on
+/// different drives or WSL distributions, no relative path exists and the method returns an
+/// [`Error::InvalidPrefix`] with the prefix of `to`.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{relative_wsl_path, Error};
+///
+/// assert_eq!(relative_wsl_path("C:\\a\\b", "C:\\a\\c\\d").unwrap(), "../c/d");
+/// assert_eq!(relative_wsl_path("C:\\a", "C:\\a\\b\\c.txt").unwrap(), "b/c.txt");
+/// assert_eq!(relative_wsl_path("C:\\a\\b\\c", "C:\\").unwrap(), "../../..");
+/// assert_eq!(relative_wsl_path("C:\\Users\\user", "c:\\users\\USER\\").unwrap(), ".");
+/// assert_eq!(
+///     relative_wsl_path("\\\\wsl$\\Ubuntu\\home\\user", "\\\\wsl.localhost\\Ubuntu\\etc\\fstab").unwrap(),
+///     "../../etc/fstab",
+/// );
+/// assert_eq!(
+///     relative_wsl_path("\\\\wsl$\\Ubuntu\\home\\Foo\\a", "\\\\wsl$\\Ubuntu\\home\\foo\\b").unwrap(),
+///     "../../foo/b",
+/// );
+///
+/// assert_eq!(
+///     relative_wsl_path("C:\\a", "D:\\b").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "D:".to_string() },
+/// );
+/// assert_eq!(
+///     relative_wsl_path("C:\\a", "\\\\wsl$\\Ubuntu\\home").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\wsl$\\Ubuntu".to_string() },
+/// );
+/// ```
+pub fn relative_wsl_path(from: &str, to: &str) -> Result<String, Error> {
+    let from_conversion = windows_to_wsl_detailed(from)?;
+    let to_conversion = windows_to_wsl_detailed(to)?;
+
+    let options = Options::default();
+    let same_root = match (&from_conversion.distro, &to_conversion.distro) {
+        (Some(from_distro), Some(to_distro)) => from_distro.eq_ignore_ascii_case(to_distro),
+        (None, None) => {
+            drive_for_mount(&from_conversion.path, &options)
+                == drive_for_mount(&to_conversion.path, &options)
+        }
+        _ => false,
+    };
+    if !same_root {
+        let prefix = Utf8WindowsPath::new(to)
+            .components()
+            .next()
+            .map(|component| component.as_str().to_string())
+            .unwrap_or_default();
+        return Err(Error::InvalidPrefix { prefix });
+    }
+
+    let from_names: Vec<_> = Utf8UnixPath::new(&from_conversion.path)
+        .components()
+        .skip(1)
+        .collect();
+    let to_names: Vec<_> = Utf8UnixPath::new(&to_conversion.path)
+        .components()
+        .skip(1)
+        .collect();
+    let case_sensitive = from_conversion.distro.is_some() && to_conversion.distro.is_some();
+    let common_length = from_names
+        .iter()
+        .zip(&to_names)
+        .take_while(|(from_name, to_name)| {
+            if case_sensitive {
+                from_name == to_name
+            } else {
+                from_name.as_str().eq_ignore_ascii_case(to_name.as_str())
+            }
+        })
+        .count();
+
+    let mut output = String::new();
+    for _ in common_length..from_names.len() {
+        output.push_str("../");
+    }
+    for name in &to_names[common_length..] {
+        output.push_str(name.as_str());
+        output.push('/');
+    }
+    output.pop();
+    if output.is_empty() {
+        output.push('.');
+    }
+    Ok(output)
+}
+
+/// Convert a Windows path to a Cygwin path (e.g. `C:\foo` to `/cygdrive/c/foo`).
+///
+/// This works like [`windows_to_wsl`], but uses `/cygdrive` as [`Options::mount_root`]. For the
+/// `/c/foo` flavor used by Git Bash and MSYS2, use [`windows_to_wsl_with`] with an empty mount
+/// root instead.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_cygwin, windows_to_wsl_with, Options};
+///
+/// assert_eq!(windows_to_cygwin("C:\\foo").unwrap(), "/cygdrive/c/foo");
+/// assert_eq!(windows_to_cygwin("D:\\").unwrap(), "/cygdrive/d");
+///
+/// let mut options = Options::default();
+/// options.mount_root = String::new();
+/// assert_eq!(windows_to_wsl_with("C:\\foo", &options).unwrap(), "/c/foo");
+/// ```
+pub fn windows_to_cygwin(windows_path: &str) -> Result<String, Error> {
+    let options = Options {
+        mount_root: CYGWIN_MOUNT_ROOT.to_string(),
+        ..Options::default()
+    };
+    windows_to_wsl_with(windows_path, &options)
+}
+
+/// Convert a Cygwin, Git Bash or MSYS2 path (e.g. `/cygdrive/c/foo` or `/c/foo`) to a Windows
+/// path.
+///
+/// Paths below `/cygdrive` are converted with `/cygdrive` as [`Options::mount_root`], all other
+/// paths with drives mounted directly under the root directory.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{cygwin_to_windows, Error};
+///
+/// assert_eq!(cygwin_to_windows("/cygdrive/c/foo").unwrap(), "C:\\foo");
+/// assert_eq!(cygwin_to_windows("/c/foo").unwrap(), "C:\\foo");
+/// assert_eq!(cygwin_to_windows("/d").unwrap(), "D:\\");
+///
+/// assert_eq!(
+///     cygwin_to_windows("/cygdrive").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/cygdrive".to_string() },
+/// );
+/// assert_eq!(
+///     cygwin_to_windows("/usr/bin").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/usr".to_string() },
+/// );
+/// ```
+pub fn cygwin_to_windows(cygwin_path: &str) -> Result<String, Error> {
+    let mount_root = if Utf8UnixPath::new(cygwin_path).starts_with(CYGWIN_MOUNT_ROOT) {
+        CYGWIN_MOUNT_ROOT
+    } else {
+        ""
+    };
+    let options = Options {
+        mount_root: mount_root.to_string(),
+        ..Options::default()
+    };
+    wsl_to_windows_with(cygwin_path, &options)
+}
+
+/// The directory where Cygwin makes Windows drives available.
+const CYGWIN_MOUNT_ROOT: &str = "/cygdrive";
+
+/// Convert a path from Windows to WSL or vice versa, depending on the kind of input path.
+///
+/// The direction of the conversion is detected as follows:
+///
+/// - Paths starting with an ASCII letter followed by a colon (e.g. `C:\...` or `C:`) or with two
+///   backslashes (e.g. `\\wsl.localhost\...` or `\\?\C:\...`) are treated as Windows paths and
+///   converted using [`windows_to_wsl`].
+/// - Paths starting with a slash (e.g. `/mnt/c/...`) are treated as WSL paths and converted using
+///   [`wsl_to_windows`].
+///
+/// # Errors
+///
+/// If the path matches neither of these rules, the method returns an [`Error::AmbiguousPath`].
+/// Otherwise, it fails in the same cases as the respective conversion function.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{convert, Error};
+///
+/// assert_eq!(convert("C:\\Windows").unwrap(), "/mnt/c/Windows");
+/// assert_eq!(convert("C:\\").unwrap(), "/mnt/c");
+/// assert_eq!(convert("\\\\?\\C:\\Windows").unwrap(), "/mnt/c/Windows");
+/// assert_eq!(convert("/mnt/c/Windows").unwrap(), "C:\\Windows");
+/// assert_eq!(convert("/mnt/c").unwrap(), "C:\\");
+///
+/// // The detected direction determines the error
+/// assert_eq!(convert("C:foo").unwrap_err(), Error::DriveRelativePath { drive: 'C' });
+/// assert_eq!(convert("\\\\?\\").unwrap_err(), Error::RelativePath);
+/// assert_eq!(convert("/etc").unwrap_err(), Error::InvalidPrefix { prefix: "/etc".to_string() });
+///
+/// // Paths that are neither Windows nor WSL paths are rejected
+/// assert_eq!(convert("foo\\bar.txt").unwrap_err(), Error::AmbiguousPath);
+/// assert_eq!(convert("foo/bar.txt").unwrap_err(), Error::AmbiguousPath);
+///
+/// // Empty paths are rejected explicitly
+/// assert_eq!(convert("").unwrap_err(), Error::EmptyPath);
+/// assert_eq!(convert(" ").unwrap_err(), Error::EmptyPath);
+/// ```
+pub fn convert(path: &str) -> Result<String, Error> {
+    if is_blank(path) {
+        return Err(Error::EmptyPath);
+    }
+    match path.as_bytes() {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => windows_to_wsl(path),
+        [b'\\', b'\\', ..] => windows_to_wsl(path),
+        [b'/', ..] => wsl_to_windows(path),
+        _ => Err(Error::AmbiguousPath),
+    }
+}
+
+/// The kind of location a Windows or WSL path refers to, as returned by [`classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PathClassification<'a> {
+    /// A WSL path located on a mounted Windows drive (e.g. `/mnt/c/Windows`).
+    WslDrive {
+        /// The drive letter, always uppercase.
+        letter: char,
+    },
+    /// A Windows path to the filesystem of a WSL distribution (e.g.
+    /// `\\wsl.localhost\Ubuntu\home`).
+    WslLocalhost {
+        /// The name of the distribution.
+        distro: &'a str,
+    },
+    /// A Windows path located on a drive (e.g. `C:\Windows` or `\\?\C:\Windows`).
+    WindowsDrive {
+        /// The drive letter, always uppercase.
+        letter: char,
+    },
+    /// A Windows path located on a network share that is not a WSL distribution (e.g.
+    /// `\\server\share\foo`).
+    UncNetwork {
+        /// The hostname of the server.
+        host: &'a str,
+    },
+    /// A relative path (e.g. `foo\bar`), including paths that are relative to the current
+    /// directory of a drive (e.g. `C:foo`).
+    Relative,
+    /// A Windows path to a device other than a drive (e.g. `\\.\PhysicalDrive0`).
+    Device,
+    /// Any other path, e.g. a WSL path that is not located on a Windows drive (like `/home/user`)
+    /// and Windows paths with an invalid prefix.
+    Unsupported,
+}
+
+/// Classify a Windows or WSL path by the location it refers to, without converting it.
+///
+/// Paths starting with `/` are treated as WSL paths (like [`convert`] does), all other paths as
+/// Windows paths. Only the prefix of the path is taken into account, and like [`is_wsl_path`],
+/// only the default mount root `/mnt` is recognized. This can be used to explain to a user why a
+/// path cannot be converted.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{classify, PathClassification};
+///
+/// assert_eq!(classify("/mnt/c/Windows"), PathClassification::WslDrive { letter: 'C' });
+/// assert_eq!(
+///     classify("\\\\wsl.localhost\\Ubuntu\\home"),
+///     PathClassification::WslLocalhost { distro: "Ubuntu" },
+/// );
+/// assert_eq!(
+///     classify("\\\\?\\UNC\\wsl$\\Debian\\home"),
+///     PathClassification::WslLocalhost { distro: "Debian" },
+/// );
+/// assert_eq!(classify("C:\\Windows"), PathClassification::WindowsDrive { letter: 'C' });
+/// assert_eq!(classify("d:/foo"), PathClassification::WindowsDrive { letter: 'D' });
+/// assert_eq!(classify("\\\\?\\C:\\Windows"), PathClassification::WindowsDrive { letter: 'C' });
+/// assert_eq!(classify("\\\\.\\C:\\Windows"), PathClassification::WindowsDrive { letter: 'C' });
+/// assert_eq!(
+///     classify("\\\\server\\share\\foo"),
+///     PathClassification::UncNetwork { host: "server" },
+/// );
+/// assert_eq!(classify("foo\\bar.txt"), PathClassification::Relative);
+/// assert_eq!(classify("C:foo"), PathClassification::Relative);
+/// assert_eq!(classify("\\\\.\\PhysicalDrive0"), PathClassification::Device);
+/// assert_eq!(classify("/home/user"), PathClassification::Unsupported);
+/// assert_eq!(classify("/mnt/wsl"), PathClassification::Unsupported);
+/// assert_eq!(classify("\\\\?\\GLOBALROOT\\Device"), PathClassification::Unsupported);
+/// assert_eq!(classify("\\\\?\\5:\\foo"), PathClassification::Unsupported);
+/// assert_eq!(classify(""), PathClassification::Unsupported);
+/// ```
+#[must_use]
+pub fn classify(path: &str) -> PathClassification<'_> {
+    if is_blank(path) {
+        PathClassification::Unsupported
+    } else if path.starts_with('/') {
+        classify_wsl_path(path)
+    } else {
+        classify_windows_path(path)
+    }
+}
+
+/// Classify a path as a WSL path (see [`classify`]).
+fn classify_wsl_path(path: &str) -> PathClassification<'_> {
+    let mut components = Utf8UnixPath::new(path).components();
+    if components.next() != Some(Utf8UnixComponent::RootDir) {
+        return PathClassification::Relative;
+    }
+
+    match (components.next(), components.next()) {
+        (Some(Utf8UnixComponent::Normal("mnt")), Some(Utf8UnixComponent::Normal(drive))) => {
+            match drive.as_bytes() {
+                [letter] if letter.is_ascii_alphabetic() => PathClassification::WslDrive {
+                    letter: char::from(letter.to_ascii_uppercase()),
+                },
+                _ => PathClassification::Unsupported,
+            }
+        }
+        _ => PathClassification::Unsupported,
+    }
+}
+
+/// Classify a path as a Windows path (see [`classify`]).
+fn classify_windows_path(path: &str) -> PathClassification<'_> {
+    let mut components = Utf8WindowsPath::new(path).components();
+    let Some(Utf8WindowsComponent::Prefix(prefix_component)) = components.next() else {
+        return PathClassification::Relative;
+    };
+
+    match prefix_component.kind() {
+        Utf8WindowsPrefix::Disk(_) if components.next() != Some(Utf8WindowsComponent::RootDir) => {
+            PathClassification::Relative
+        }
+        // The drive letter returned by `typed_path` is always uppercase
+        Utf8WindowsPrefix::Disk(letter) | Utf8WindowsPrefix::VerbatimDisk(letter) => {
+            PathClassification::WindowsDrive { letter }
+        }
+        Utf8WindowsPrefix::UNC(_, "") | Utf8WindowsPrefix::VerbatimUNC(_, "") => {
+            PathClassification::Unsupported
+        }
+        Utf8WindowsPrefix::UNC(hostname, share)
+        | Utf8WindowsPrefix::VerbatimUNC(hostname, share)
+            if is_wsl_hostname(hostname) =>
+        {
+            PathClassification::WslLocalhost { distro: share }
+        }
+        Utf8WindowsPrefix::UNC(hostname, _) | Utf8WindowsPrefix::VerbatimUNC(hostname, _) => {
+            PathClassification::UncNetwork { host: hostname }
+        }
+        Utf8WindowsPrefix::Verbatim(name) | Utf8WindowsPrefix::DeviceNS(name)
+            if name.chars().count() == 2 && name.ends_with(':') =>
+        {
+            match name.as_bytes()[0] {
+                letter if letter.is_ascii_alphabetic() => PathClassification::WindowsDrive {
+                    letter: char::from(letter.to_ascii_uppercase()),
+                },
+                // Drives with invalid letters (e.g. `\\.\5:`) are not devices
+                _ => PathClassification::Unsupported,
+            }
+        }
+        Utf8WindowsPrefix::DeviceNS(_) => PathClassification::Device,
+        Utf8WindowsPrefix::Verbatim(_) => PathClassification::Unsupported,
+    }
+}
+
+/// Returns `true` if the path is an absolute Windows path starting with a drive letter (e.g.
+/// `C:\Windows`) or a UNC prefix (e.g. `\\wsl.localhost\Ubuntu\home` or `\\?\C:\Windows`).
+///
+/// This is a cheap check that neither allocates nor normalizes the path. It does not guarantee
+/// that the path can actually be converted.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::is_windows_path;
+///
+/// assert!(is_windows_path("C:\\Windows"));
+/// assert!(is_windows_path("C:/Windows"));
+/// assert!(is_windows_path("C:\\"));
+/// assert!(is_windows_path("\\\\?\\C:\\Windows"));
+/// assert!(is_windows_path("\\\\wsl.localhost\\Ubuntu\\home"));
+///
+/// assert!(!is_windows_path("C"));
+/// assert!(!is_windows_path("C:"));
+/// assert!(!is_windows_path("C:foo"));
+/// assert!(!is_windows_path("\\\\server"));
+/// assert!(!is_windows_path("foo\\bar.txt"));
+/// assert!(!is_windows_path("/mnt/c/Windows"));
+/// ```
+#[must_use]
+pub fn is_windows_path(path: &str) -> bool {
+    match path.as_bytes() {
+        [drive, b':', b'\\' | b'/', ..] => drive.is_ascii_alphabetic(),
+        [b'\\', b'\\', ..] => Utf8WindowsPath::new(path).is_absolute(),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the path is an absolute WSL path below `/mnt/<driveletter>` (e.g.
+/// `/mnt/c/Windows`).
+///
+/// This is a cheap check that neither allocates nor normalizes the path, and only takes the
+/// default mount root `/mnt` into account. It does not guarantee that the path can actually be
+/// converted.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::is_wsl_path;
+///
+/// assert!(is_wsl_path("/mnt/c"));
+/// assert!(is_wsl_path("/mnt/c/"));
+/// assert!(is_wsl_path("/mnt/c/Windows"));
+///
+/// assert!(!is_wsl_path("/mnt"));
+/// assert!(!is_wsl_path("/mnt/"));
+/// assert!(!is_wsl_path("/mntx/c"));
+/// assert!(!is_wsl_path("/mnt/wsl"));
+/// assert!(!is_wsl_path("/etc/fstab"));
+/// assert!(!is_wsl_path("mnt/c/Windows"));
+/// assert!(!is_wsl_path("C:\\Windows"));
+/// ```
+#[must_use]
+pub fn is_wsl_path(path: &str) -> bool {
+    matches!(classify_wsl_path(path), PathClassification::WslDrive { .. })
+}
+
+/// Returns `true` if the Windows path can be converted to a WSL path with [`windows_to_wsl`].
+///
+/// Unlike [`is_windows_path`], this takes the same decisions as the conversion with the
+/// [default options](Options::default): drive paths (including verbatim drives and drives in the
+/// device namespace) and paths to the filesystem of a WSL distribution are convertible, while
+/// network shares, devices and relative paths are not. The prefix is classified without
+/// allocating the converted path, so this can be used to check many paths cheaply (e.g. to
+/// disable a "copy as WSL path" action in a user interface).
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::is_convertible_to_wsl;
+///
+/// assert!(is_convertible_to_wsl("C:\\Windows"));
+/// assert!(is_convertible_to_wsl("\\\\?\\C:\\Windows"));
+/// assert!(is_convertible_to_wsl("\\\\.\\C:\\Windows"));
+/// assert!(is_convertible_to_wsl("\\\\wsl.localhost\\Ubuntu\\home"));
+/// assert!(is_convertible_to_wsl("\\\\?\\UNC\\wsl$\\Ubuntu\\home"));
+///
+/// assert!(!is_convertible_to_wsl("\\\\server\\share\\foo"));
+/// assert!(!is_convertible_to_wsl("\\\\wsl$\\\\Ubuntu"));
+/// assert!(!is_convertible_to_wsl("\\\\.\\PhysicalDrive0"));
+/// assert!(!is_convertible_to_wsl("\\\\?\\5:\\foo"));
+/// assert!(!is_convertible_to_wsl("C:foo"));
+/// assert!(!is_convertible_to_wsl("foo\\bar.txt"));
+/// assert!(!is_convertible_to_wsl(""));
+/// ```
+#[must_use]
+pub fn is_convertible_to_wsl(windows_path: &str) -> bool {
+    Utf8WindowsPath::new(windows_path).is_absolute()
+        && matches!(
+            classify_windows_path(windows_path),
+            PathClassification::WindowsDrive { .. } | PathClassification::WslLocalhost { .. }
+        )
+}
+
+/// Returns `true` if the WSL path can be converted to a Windows path with [`wsl_to_windows`].
+///
+/// Unlike [`is_wsl_path`], this takes the same decisions as the conversion with the
+/// [default options](Options::default), i.e. it also rejects paths with file names that contain
+/// a backslash. Like [`is_convertible_to_wsl`], it does not allocate the converted path.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::is_convertible_to_windows;
+///
+/// assert!(is_convertible_to_windows("/mnt/c"));
+/// assert!(is_convertible_to_windows("/mnt/c/Windows"));
+/// assert!(is_convertible_to_windows("/mnt/C/Windows"));
+///
+/// assert!(!is_convertible_to_windows("/mnt"));
+/// assert!(!is_convertible_to_windows("/mnt/wsl/foo"));
+/// assert!(!is_convertible_to_windows("/mnt/5/foo"));
+/// assert!(!is_convertible_to_windows("/mnt/c/a\\b"));
+/// assert!(!is_convertible_to_windows("/home/user"));
+/// assert!(!is_convertible_to_windows("mnt/c/Windows"));
+/// ```
+#[must_use]
+pub fn is_convertible_to_windows(wsl_path: &str) -> bool {
+    is_wsl_path(wsl_path) && !wsl_path.contains('\\')
+}
+
+/// Get the mount point of a Windows drive inside WSL.
+///
+/// The mount point is located below [`Options::mount_root`], and the case of the drive letter is
+/// determined by [`Options::drive_case`].
+///
+/// # Errors
+///
+/// If the drive letter is not `A`-`Z`, the method returns an [`Error::InvalidDriveLetter`]. If
+/// [`Options::automount_enabled`] is `false`, it fails with [`Error::AutomountDisabled`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{mount_point_for_drive, Error, Options};
+///
+/// let mut options = Options::default();
+/// assert_eq!(mount_point_for_drive('C', &options).unwrap(), "/mnt/c");
+/// assert_eq!(mount_point_for_drive('5', &options).unwrap_err(), Error::InvalidDriveLetter { got: "5".to_string() });
+///
+/// options.mount_root = "/".to_string();
+/// assert_eq!(mount_point_for_drive('d', &options).unwrap(), "/d");
+///
+/// options.automount_enabled = false;
+/// assert_eq!(mount_point_for_drive('C', &options).unwrap_err(), Error::AutomountDisabled);
+/// ```
+pub fn mount_point_for_drive(drive: char, options: &Options) -> Result<String, Error> {
+    let mut output = Vec::new();
+    push_mount_point(&mut output, drive, options)?;
+    String::from_utf8(output).map_err(|_| Error::InvalidUtf8)
+}
+
+/// Get the Windows drive letter of a WSL path located on a mounted drive.
+///
+/// The case of the drive letter is determined by [`Options::windows_drive_case`], i.e. it matches
+/// the drive letter of the path converted by [`wsl_to_windows_with`]. Returns `None` if the path
+/// is not located below [`Options::mount_root`] or if [`Options::automount_enabled`] is `false`.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{drive_for_mount, Options};
+///
+/// let mut options = Options::default();
+/// assert_eq!(drive_for_mount("/mnt/c/Windows", &options), Some('C'));
+/// assert_eq!(drive_for_mount("/mnt/d", &options), Some('D'));
+/// assert_eq!(drive_for_mount("/mnt/5", &options), None);
+/// assert_eq!(drive_for_mount("/mnt/wsl", &options), None);
+/// assert_eq!(drive_for_mount("/home/user", &options), None);
+///
+/// options.mount_root = "/windows".to_string();
+/// assert_eq!(drive_for_mount("/windows/e/foo", &options), Some('E'));
+/// assert_eq!(drive_for_mount("/mnt/c/Windows", &options), None);
+/// ```
+#[must_use]
+pub fn drive_for_mount(path: &str, options: &Options) -> Option<char> {
+    if !options.automount_enabled {
+        return None;
+    }
+
+    let mut components = UnixPath::new(path).components();
+    if components.next() != Some(UnixComponent::RootDir) {
+        return None;
+    }
+
+    let (mut components, _) = strip_mount_point(components, &options.mount_root).ok()?;
+    match components.next() {
+        Some(UnixComponent::Normal(drive)) if is_drive_mount(drive, options) => {
+            let drive = parse_drive_letter(drive).ok()?;
+            Some(options.windows_drive_case.apply(drive))
+        }
+        _ => None,
+    }
+}
+
+/// Split a Windows path on a drive into the uppercase drive letter and the normalized remainder
+/// of the path.
+///
+/// The remainder is `\` for the root of the drive.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`]. Paths that are not located on a
+/// drive (e.g. `\\wsl.localhost\Ubuntu\home`) lead to an [`Error::InvalidPrefix`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{split_windows, Error};
+///
+/// assert_eq!(split_windows("C:\\Windows").unwrap(), ('C', "\\Windows".to_string()));
+/// assert_eq!(split_windows("d:\\foo\\..\\bar\\baz.txt").unwrap(), ('D', "\\bar\\baz.txt".to_string()));
+/// assert_eq!(split_windows("\\\\?\\E:\\foo").unwrap(), ('E', "\\foo".to_string()));
+/// assert_eq!(split_windows("C:\\").unwrap(), ('C', "\\".to_string()));
+///
+/// assert_eq!(
+///     split_windows("\\\\wsl.localhost\\Ubuntu\\home").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\wsl.localhost\\Ubuntu".to_string() },
+/// );
+/// assert_eq!(split_windows("C:foo").unwrap_err(), Error::DriveRelativePath { drive: 'C' });
+/// ```
+pub fn split_windows(windows_path: &str) -> Result<(char, String), Error> {
+    let conversion = windows_to_wsl_detailed(windows_path)?;
+    let split = match conversion.distro {
+        Some(_) => None,
+        None => split_wsl(&conversion.path).ok(),
+    };
+    let Some((drive, remainder)) = split else {
+        let prefix = Utf8WindowsPath::new(windows_path)
+            .components()
+            .next()
+            .map(|component| component.as_str().to_string())
+            .unwrap_or_default();
+        return Err(Error::InvalidPrefix { prefix });
+    };
+
+    let remainder = if remainder.is_empty() {
+        String::from("\\")
+    } else {
+        remainder.replace('/', "\\")
+    };
+    Ok((drive.to_ascii_uppercase(), remainder))
+}
+
+/// Split a WSL path on a mounted drive into the lowercase drive letter and the normalized
+/// remainder of the path below the mount point.
+///
+/// The remainder is empty for the mount point of the drive itself.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{split_wsl, Error};
+///
+/// assert_eq!(split_wsl("/mnt/c/Windows").unwrap(), ('c', "/Windows".to_string()));
+/// assert_eq!(split_wsl("/mnt/d/foo/../bar/baz.txt").unwrap(), ('d', "/bar/baz.txt".to_string()));
+/// assert_eq!(split_wsl("/mnt/c").unwrap(), ('c', String::new()));
+/// assert_eq!(split_wsl("/mnt/c/").unwrap(), ('c', String::new()));
+///
+/// assert_eq!(split_wsl("/home/user").unwrap_err(), Error::InvalidPrefix { prefix: "/home".to_string() });
+/// ```
+pub fn split_wsl(wsl_path: &str) -> Result<(char, String), Error> {
+    let windows_path = wsl_to_windows(wsl_path)?;
+    let drive = windows_path.chars().next().unwrap_or_default();
+
+    // Backslashes in file names are rejected during conversion, so all backslashes in the
+    // converted path are separators.
+    let remainder = match &windows_path[drive.len_utf8() + 1..] {
+        "\\" => String::new(),
+        remainder => remainder.replace('\\', "/"),
+    };
+    Ok((drive.to_ascii_lowercase(), remainder))
+}
+
+/// Join a WSL directory and a file name with a single `/` in between.
+///
+/// Trailing separators of the directory and leading separators of the name are collapsed, so
+/// the result never contains a double separator at the join. The path is neither converted nor
+/// normalized. An empty directory yields the name and an empty name yields the directory.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::join_wsl;
+///
+/// assert_eq!(join_wsl("/mnt/c/Users", "foo.txt"), "/mnt/c/Users/foo.txt");
+/// assert_eq!(join_wsl("/mnt/c/Users/", "foo.txt"), "/mnt/c/Users/foo.txt");
+/// assert_eq!(join_wsl("/mnt/c/Users//", "/foo.txt"), "/mnt/c/Users/foo.txt");
+/// assert_eq!(join_wsl("/", "home"), "/home");
+/// assert_eq!(join_wsl("foo", "bar/"), "foo/bar/");
+/// assert_eq!(join_wsl("", "foo.txt"), "foo.txt");
+/// assert_eq!(join_wsl("/mnt/c/", ""), "/mnt/c/");
+/// ```
+#[must_use]
+pub fn join_wsl(dir_wsl: &str, name: &str) -> String {
+    if dir_wsl.is_empty() || name.is_empty() {
+        return format!("{dir_wsl}{name}");
+    }
+    let dir = dir_wsl.trim_end_matches('/');
+    let name = name.trim_start_matches('/');
+    format!("{dir}/{name}")
+}
+
+/// Join a Windows directory and a file name with a single separator in between.
+///
+/// Both `\` and `/` are treated as separators. The name is joined with the separator that the
+/// directory already uses last, or `\` if it contains none. Trailing separators of the directory
+/// and leading separators of the name are collapsed, so the result never contains a double
+/// separator at the join. A drive-relative directory like `C:` is joined without a separator,
+/// since `C:\foo` would refer to a different file than `C:foo`. The path is neither converted
+/// nor normalized, and an empty directory or name yields the other argument unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::join_windows;
+///
+/// assert_eq!(join_windows("C:\\Users", "foo.txt"), "C:\\Users\\foo.txt");
+/// assert_eq!(join_windows("C:\\Users\\", "foo.txt"), "C:\\Users\\foo.txt");
+/// assert_eq!(join_windows("C:\\Users\\\\", "\\foo.txt"), "C:\\Users\\foo.txt");
+/// assert_eq!(join_windows("C:\\", "Windows"), "C:\\Windows");
+/// assert_eq!(join_windows("C:/Users/", "foo.txt"), "C:/Users/foo.txt");
+/// assert_eq!(join_windows("\\\\wsl.localhost\\Ubuntu", "home"), "\\\\wsl.localhost\\Ubuntu\\home");
+/// assert_eq!(join_windows("C:", "foo.txt"), "C:foo.txt");
+/// assert_eq!(join_windows("", "foo.txt"), "foo.txt");
+/// assert_eq!(join_windows("C:\\Users\\", ""), "C:\\Users\\");
+/// ```
+#[must_use]
+pub fn join_windows(dir_windows: &str, name: &str) -> String {
+    let is_separator = |c: char| c == '\\' || c == '/';
+    if dir_windows.is_empty() || name.is_empty() {
+        return format!("{dir_windows}{name}");
+    }
+    let name = name.trim_start_matches(is_separator);
+    if let [drive, b':'] = dir_windows.as_bytes() {
+        if drive.is_ascii_alphabetic() {
+            return format!("{dir_windows}{name}");
+        }
+    }
+    let separator = dir_windows
+        .chars()
+        .rfind(|&c| is_separator(c))
+        .unwrap_or('\\');
+    let dir = dir_windows.trim_end_matches(is_separator);
+    format!("{dir}{separator}{name}")
+}
+
+/// Returns `true` if converting a Windows path to a WSL path and back yields the original path.
+///
+/// The paths are compared in normalized form, i.e. differences in `.` and `..` components,
+/// separators, the `\\?\` prefix and the case of drive letters and hostnames are ignored. Paths
+/// to the filesystem of a WSL distribution are converted back using [`wsl_to_windows_unc`], so
+/// the legacy `\\wsl$` host is considered equal to `\\wsl.localhost`. Paths that cannot be
+/// converted do not round-trip.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::round_trips;
+///
+/// assert!(round_trips("C:\\Windows\\System32"));
+/// assert!(round_trips("C:\\"));
+/// assert!(round_trips("c:/Windows/"));
+/// assert!(round_trips("C:\\foo\\..\\bar\\.\\baz.txt"));
+/// assert!(round_trips("C:\\..\\Windows"));
+/// assert!(round_trips("\\\\?\\C:\\Windows"));
+/// assert!(round_trips("\\\\wsl$\\Ubuntu\\home\\user"));
+/// assert!(round_trips("\\\\wsl.localhost\\Ubuntu\\"));
+///
+/// assert!(!round_trips("\\\\server\\share\\foo"));
+/// assert!(!round_trips("C:foo"));
+/// ```
+#[must_use]
+pub fn round_trips(windows_path: &str) -> bool {
+    let Ok(conversion) = windows_to_wsl_detailed(windows_path) else {
+        return false;
+    };
+    let converted_back = match &conversion.distro {
+        Some(distro) => wsl_to_windows_unc(&conversion.path, distro),
+        None => wsl_to_windows(&conversion.path),
+    };
+    converted_back.is_ok_and(|converted_back| {
+        normalize_windows_path(&converted_back) == normalize_windows_path(windows_path)
+    })
+}
+
+/// Returns `true` if converting a WSL path to a Windows path and back yields the original path.
+///
+/// The paths are compared in normalized form, i.e. differences in `.` and `..` components and
+/// repeated or trailing slashes are ignored. Paths that cannot be converted do not round-trip.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::wsl_round_trips;
+///
+/// assert!(wsl_round_trips("/mnt/c/Windows/System32"));
+/// assert!(wsl_round_trips("/mnt/c/"));
+/// assert!(wsl_round_trips("/mnt/c/foo/../bar//baz.txt"));
+/// assert!(wsl_round_trips("/mnt/c/foo/a:b"));
+///
+/// // Drive letters are lowercased and backslashes cannot be converted
+/// assert!(!wsl_round_trips("/mnt/C/Windows"));
+/// assert!(!wsl_round_trips("/mnt/c/foo\\bar"));
+///
+/// // Paths escaping the drive root are clamped
+/// assert!(!wsl_round_trips("/mnt/c/../../etc"));
+///
+/// assert!(!wsl_round_trips("/home/user"));
+/// ```
+#[must_use]
+pub fn wsl_round_trips(wsl_path: &str) -> bool {
+    wsl_to_windows(wsl_path)
+        .and_then(|windows_path| windows_to_wsl(&windows_path))
+        .is_ok_and(|converted_back| {
+            Utf8UnixPath::new(&converted_back).normalize()
+                == Utf8UnixPath::new(wsl_path).normalize()
+        })
+}
+
+/// Returns `true` if two Windows paths refer to the same location, ignoring case.
+///
+/// The paths are compared in normalized form like in [`round_trips`], i.e. differences in `.`
+/// and `..` components, separators, the `\\?\` prefix and the legacy `\\wsl$` host are ignored.
+/// Afterwards, the paths are compared case-insensitively, like Windows filesystems do by
+/// default. Paths on the filesystem of a WSL distribution (e.g. `\\wsl.localhost\Ubuntu\home`)
+/// are case-sensitive, so only their host and distribution name are compared case-insensitively.
+/// A path with a root directory (e.g. `C:\foo`) never equals one without (e.g. `C:foo`).
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::paths_equal_windows;
+///
+/// assert!(paths_equal_windows("C:\\Foo", "c:\\foo"));
+/// assert!(paths_equal_windows("C:\\Users\\Ärger", "c:/users/ärger/"));
+/// assert!(paths_equal_windows("\\\\?\\C:\\Windows", "C:\\foo\\..\\WINDOWS\\."));
+/// assert!(paths_equal_windows("\\\\wsl$\\Ubuntu\\home", "\\\\WSL.localhost\\ubuntu\\home"));
+///
+/// assert!(!paths_equal_windows("C:\\foo", "D:\\foo"));
+/// assert!(!paths_equal_windows("C:\\foo", "C:\\foo\\bar"));
+/// assert!(!paths_equal_windows("C:\\foo", "C:foo"));
+/// assert!(!paths_equal_windows("\\\\wsl.localhost\\Ubuntu\\home\\Foo", "\\\\wsl.localhost\\Ubuntu\\home\\foo"));
+/// ```
+#[must_use]
+pub fn paths_equal_windows(a: &str, b: &str) -> bool {
+    if Utf8WindowsPath::new(a).has_root() != Utf8WindowsPath::new(b).has_root() {
+        return false;
+    }
+
+    let (a, b) = (normalize_windows_path(a), normalize_windows_path(b));
+    let (a_prefix, a_rest, case_sensitive) = split_windows_prefix(&a);
+    let (b_prefix, b_rest, _) = split_windows_prefix(&b);
+    fold_case(a_prefix).eq(fold_case(b_prefix))
+        && if case_sensitive {
+            a_rest == b_rest
+        } else {
+            fold_case(a_rest).eq(fold_case(b_rest))
+        }
+}
+
+/// Split a Windows path into its prefix (e.g. `C:` or `\\server\share`) and the rest of the
+/// path.
+///
+/// Also returns whether the rest of the path is case-sensitive, i.e. whether it is located on the
+/// filesystem of a WSL distribution (e.g. `\\wsl.localhost\Ubuntu`).
+fn split_windows_prefix(path: &str) -> (&str, &str, bool) {
+    let Some(Utf8WindowsComponent::Prefix(prefix_component)) =
+        Utf8WindowsPath::new(path).components().next()
+    else {
+        return ("", path, false);
+    };
+    let case_sensitive = matches!(
+        prefix_component.kind(),
+        Utf8WindowsPrefix::UNC(hostname, _) | Utf8WindowsPrefix::VerbatimUNC(hostname, _)
+            if is_wsl_hostname(hostname)
+    );
+    let (prefix, rest) = path.split_at(prefix_component.as_str().len());
+    (prefix, rest, case_sensitive)
+}
+
+/// Returns the characters of a string in uppercase, for comparing strings case-insensitively.
+fn fold_case(string: &str) -> impl Iterator<Item = char> + '_ {
+    string.chars().flat_map(char::to_uppercase)
+}
+
+/// Returns `true` if two WSL paths refer to the same location.
+///
+/// The paths are compared in normalized form like in [`wsl_round_trips`], i.e. differences in
+/// `.` and `..` components and repeated or trailing slashes are ignored. Unlike
+/// [`paths_equal_windows`], the comparison is case-sensitive, because Linux filesystems are.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::paths_equal_wsl;
+///
+/// assert!(paths_equal_wsl("/mnt/c/Foo", "/mnt/c/Foo"));
+/// assert!(paths_equal_wsl("/mnt/c/Foo/", "/mnt/c/bar/../Foo"));
+/// assert!(paths_equal_wsl("//home//user/.", "/home/user"));
+///
+/// assert!(!paths_equal_wsl("/mnt/c/Foo", "/mnt/c/foo"));
+/// assert!(!paths_equal_wsl("/home/user", "home/user"));
+/// ```
+#[must_use]
+pub fn paths_equal_wsl(a: &str, b: &str) -> bool {
+    Utf8UnixPath::new(a).normalize() == Utf8UnixPath::new(b).normalize()
+}
+
+/// Normalize a Windows path for comparison in [`round_trips`] and [`paths_equal_windows`].
+fn normalize_windows_path(path: &str) -> String {
+    let mut prefix = String::new();
+    let mut names = Vec::new();
+    for component in Utf8WindowsPath::new(path).components() {
+        match component {
+            Utf8WindowsComponent::Prefix(prefix_component) => match prefix_component.kind() {
+                Utf8WindowsPrefix::Disk(drive) | Utf8WindowsPrefix::VerbatimDisk(drive) => {
+                    prefix = format!("{}:", drive.to_ascii_uppercase());
+                }
+                Utf8WindowsPrefix::UNC(hostname, share)
+                | Utf8WindowsPrefix::VerbatimUNC(hostname, share) => {
+                    let hostname = if is_wsl_hostname(hostname) {
+                        "wsl.localhost".to_string()
+                    } else {
+                        hostname.to_ascii_lowercase()
+                    };
+                    prefix = format!(r"\\{hostname}\{share}");
+                }
+                _ => prefix = prefix_component.as_str().to_string(),
+            },
+            Utf8WindowsComponent::RootDir | Utf8WindowsComponent::CurDir => (),
+            Utf8WindowsComponent::Normal(name) => names.push(name),
+            Utf8WindowsComponent::ParentDir => {
+                names.pop();
+            }
+        }
+    }
+    format!(r"{prefix}\{}", names.join(r"\"))
+}
+
+/// Convert multiple Windows paths to WSL paths.
+///
+/// Each path is converted using [`windows_to_wsl`]. Failing conversions do not abort the batch,
+/// instead the result for each path is returned in the same order as the input.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_all, Error};
+///
+/// let results = windows_to_wsl_all(["C:\\Windows", "foo\\bar.txt", "D:\\baz.txt"]);
+/// assert_eq!(
+///     results,
+///     vec![Ok("/mnt/c/Windows".to_string()), Err(Error::RelativePath), Ok("/mnt/d/baz.txt".to_string())],
+/// );
+/// ```
+pub fn windows_to_wsl_all<I, S>(windows_paths: I) -> Vec<Result<String, Error>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    windows_paths
+        .into_iter()
+        .map(|windows_path| windows_to_wsl(windows_path.as_ref()))
+        .collect()
+}
+
+/// Convert multiple WSL paths to Windows paths.
+///
+/// Each path is converted using [`wsl_to_windows`]. Failing conversions do not abort the batch,
+/// instead the result for each path is returned in the same order as the input.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_all, Error};
+///
+/// let paths = vec!["/mnt/c/Windows".to_string(), "foo/bar.txt".to_string()];
+/// let results = wsl_to_windows_all(&paths);
+/// assert_eq!(results, vec![Ok("C:\\Windows".to_string()), Err(Error::RelativePath)]);
+/// ```
+pub fn wsl_to_windows_all<I, S>(wsl_paths: I) -> Vec<Result<String, Error>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    wsl_paths
+        .into_iter()
+        .map(|wsl_path| wsl_to_windows(wsl_path.as_ref()))
+        .collect()
+}
+
+/// Lazily convert Windows paths to WSL paths.
+///
+/// This works like [`windows_to_wsl_all`], but returns an iterator that converts each path only
+/// when the next result is requested, so that large inputs do not need to be collected first.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::Cell;
+/// use wslpath_rs::{windows_to_wsl_iter, Error};
+///
+/// let mut results = windows_to_wsl_iter(["C:\\Windows", "foo\\bar.txt"]);
+/// assert_eq!(results.next(), Some(Ok("/mnt/c/Windows".to_string())));
+/// assert_eq!(results.next(), Some(Err(Error::RelativePath)));
+/// assert_eq!(results.next(), None);
+///
+/// // Paths are only produced and converted on demand
+/// let produced = Cell::new(0);
+/// let paths = (0..).map(|i| {
+///     produced.set(produced.get() + 1);
+///     format!("C:\\files\\{i}.txt")
+/// });
+/// let mut results = windows_to_wsl_iter(paths);
+/// assert_eq!(produced.get(), 0);
+/// assert_eq!(results.nth(99_999), Some(Ok("/mnt/c/files/99999.txt".to_string())));
+/// assert_eq!(produced.get(), 100_000);
+/// ```
+pub fn windows_to_wsl_iter<I, S>(windows_paths: I) -> impl Iterator<Item = Result<String, Error>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    windows_paths
+        .into_iter()
+        .map(|windows_path| windows_to_wsl(windows_path.as_ref()))
+}
+
+/// Lazily convert WSL paths to Windows paths.
+///
+/// This works like [`wsl_to_windows_all`], but returns an iterator that converts each path only
+/// when the next result is requested, so that large inputs do not need to be collected first.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_iter, Error};
+///
+/// let paths = (0..).map(|i| format!("/mnt/c/files/{i}.txt"));
+/// let results: Vec<_> = wsl_to_windows_iter(paths).skip(5).take(2).collect();
+/// assert_eq!(results, vec![Ok("C:\\files\\5.txt".to_string()), Ok("C:\\files\\6.txt".to_string())]);
+///
+/// assert_eq!(wsl_to_windows_iter(["foo/bar.txt"]).next(), Some(Err(Error::RelativePath)));
+/// ```
+pub fn wsl_to_windows_iter<I, S>(wsl_paths: I) -> impl Iterator<Item = Result<String, Error>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    wsl_paths
+        .into_iter()
+        .map(|wsl_path| wsl_to_windows(wsl_path.as_ref()))
 }
@@ -0,0 +1,291 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Command-line tool that converts paths between WSL guest and Windows host.
+//!
+//! The tool accepts the same flags as Microsoft's `wslpath`, but does not need to spawn
+//! `wsl.exe` for each conversion.
+
+#![warn(unsafe_code)]
+#![warn(missing_docs)]
+#![cfg_attr(not(debug_assertions), deny(warnings))]
+#![deny(rust_2018_idioms)]
+#![deny(rust_2021_compatibility)]
+#![deny(missing_debug_implementations)]
+#![deny(rustdoc::broken_intra_doc_links)]
+#![deny(clippy::all)]
+#![deny(clippy::explicit_deref_methods)]
+#![deny(clippy::explicit_into_iter_loop)]
+#![deny(clippy::explicit_iter_loop)]
+#![deny(clippy::must_use_candidate)]
+#![cfg_attr(not(debug_assertions), deny(clippy::used_underscore_binding))]
+
+use std::io::{BufRead, IsTerminal, Write};
+use std::process::ExitCode;
+use wslpath_rs::{windows_to_wsl, wsl_to_windows_with, Error, Options, Separator};
+
+/// The usage information printed for `-h`/`--help` and on invalid arguments.
+const USAGE: &str = "\
+Usage: wslpath-rs [-a] [-u|-w|-m] <path>
+       wslpath-rs [-a] [-u|-w|-m] [-0] --stdin
+
+    -a           force result to absolute path format (relative paths are resolved against
+                 the current directory)
+    -u           translate from a Windows path to a WSL path (default)
+    -w           translate from a WSL path to a Windows path
+    -m           translate from a WSL path to a Windows path, with '/' instead of '\\'
+    --stdin      read paths from stdin, one per line (default if no path is given and stdin
+                 is not a terminal)
+    -0, --null   separate input and output paths by NUL characters instead of newlines
+
+Exit status:
+    0            all paths were converted
+    1            invalid arguments or another error
+    2            a path is relative to the root or a drive and cannot be converted (e.g. `C:foo`)
+    3            a path has a prefix that cannot be converted (e.g. a network share)";
+
+/// The exit status for paths that are relative and cannot be converted.
+const EXIT_RELATIVE_PATH: u8 = 2;
+
+/// The exit status for paths with a prefix that cannot be converted.
+const EXIT_INVALID_PREFIX: u8 = 3;
+
+/// The direction of the conversion, named after the output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Convert a Windows path to a WSL path (`-u`).
+    Wsl,
+    /// Convert a WSL path to a Windows path (`-w`).
+    Windows,
+    /// Convert a WSL path to a Windows path with forward slashes (`-m`).
+    Mixed,
+}
+
+/// The parsed command-line arguments.
+#[derive(Debug)]
+struct Args {
+    /// Whether relative input paths are resolved against the current directory (`-a`).
+    absolute: bool,
+    /// The direction of the conversion.
+    mode: Mode,
+    /// Whether paths are separated by NUL characters instead of newlines in stdin mode (`-0`).
+    null: bool,
+    /// The path to convert, or `None` to read paths from stdin.
+    path: Option<String>,
+}
+
+impl Args {
+    /// Parse the command-line arguments (without the program name).
+    ///
+    /// Returns `Ok(None)` if the usage information was requested.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut absolute = false;
+        let mut mode = Mode::Wsl;
+        let mut null = false;
+        let mut stdin = false;
+        let mut path = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--stdin" => stdin = true,
+                "--null" => null = true,
+                "--" => {
+                    path = args.next();
+                    break;
+                }
+                flags if flags.len() > 1 && flags.starts_with('-') && path.is_none() => {
+                    for flag in flags.chars().skip(1) {
+                        match flag {
+                            'a' => absolute = true,
+                            'u' => mode = Mode::Wsl,
+                            'w' => mode = Mode::Windows,
+                            'm' => mode = Mode::Mixed,
+                            '0' => null = true,
+                            _ => return Err(format!("invalid option -- '{flag}'")),
+                        }
+                    }
+                }
+                _ if path.is_none() => path = Some(arg),
+                _ => return Err("too many arguments".to_string()),
+            }
+        }
+
+        if args.next().is_some() {
+            return Err("too many arguments".to_string());
+        }
+
+        if stdin && path.is_some() {
+            return Err("path argument cannot be used with --stdin".to_string());
+        }
+        if path.is_none() && !stdin && std::io::stdin().is_terminal() {
+            return Err("missing path argument".to_string());
+        }
+
+        Ok(Some(Self {
+            absolute,
+            mode,
+            null,
+            path,
+        }))
+    }
+}
+
+/// Convert a path according to the arguments.
+///
+/// Like Microsoft's `wslpath`, relative paths are translated to relative paths, unless `-a` is
+/// given, in which case the result is resolved against the current directory.
+fn convert(path: &str, args: &Args) -> Result<String, Box<dyn std::error::Error>> {
+    if args.mode == Mode::Wsl {
+        let wsl_path = match windows_to_wsl(path) {
+            // Paths relative to the root or the current directory of a drive (e.g. `\foo` or
+            // `C:foo`) have no WSL equivalent.
+            Err(Error::RelativePath) if !path.starts_with(['\\', '/']) => path.replace('\\', "/"),
+            result => result?,
+        };
+        if args.absolute && !wsl_path.starts_with('/') {
+            return current_dir_join(&wsl_path);
+        }
+        return Ok(wsl_path);
+    }
+
+    let mut options = Options::default();
+    let mut separator = '\\';
+    if args.mode == Mode::Mixed {
+        options.separator = Separator::ForwardSlash;
+        separator = '/';
+    }
+
+    if args.absolute && !path.starts_with('/') {
+        return Ok(wsl_to_windows_with(&current_dir_join(path)?, &options)?);
+    }
+
+    match wsl_to_windows_with(path, &options) {
+        Err(Error::RelativePath) => Ok(relative_wsl_to_windows(path, separator)?),
+        result => Ok(result?),
+    }
+}
+
+/// Join a relative WSL path to the current directory.
+fn current_dir_join(path: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let path = std::env::current_dir()?.join(path);
+    let path = path
+        .into_os_string()
+        .into_string()
+        .map_err(|_| "current directory is not valid UTF-8")?;
+    Ok(path)
+}
+
+/// Translate a relative WSL path to a relative Windows path by replacing the separators.
+///
+/// Like [`wsl_to_windows`](wslpath_rs::wsl_to_windows), components that contain a backslash are
+/// rejected, because Windows would treat the backslash as separator.
+fn relative_wsl_to_windows(path: &str, separator: char) -> Result<String, Error> {
+    let mut output = String::with_capacity(path.len());
+    for (index, name) in path.split('/').enumerate() {
+        if name.contains('\\') {
+            return Err(Error::InvalidWindowsComponent {
+                component: name.to_string(),
+                ch: '\\',
+            });
+        }
+        if index > 0 {
+            output.push(separator);
+        }
+        output.push_str(name);
+    }
+    Ok(output)
+}
+
+/// Report a path that could not be converted on stderr and return the exit status for the error.
+///
+/// Unsupported prefixes are followed by a hint which prefixes can be converted.
+fn report(path: &str, error: &(dyn std::error::Error + 'static), mode: Mode) -> u8 {
+    eprintln!("wslpath-rs: {path}: {error}");
+    match error.downcast_ref::<Error>() {
+        Some(Error::RelativePath | Error::DriveRelativePath { .. }) => EXIT_RELATIVE_PATH,
+        Some(Error::InvalidPrefix { .. } | Error::DevicePath { .. }) => {
+            let supported = match mode {
+                Mode::Wsl => {
+                    "drive letters (e.g. `C:\\`) and WSL hosts (e.g. `\\\\wsl.localhost\\Ubuntu`)"
+                }
+                Mode::Windows | Mode::Mixed => "the mount points of drives (e.g. `/mnt/c`)",
+            };
+            eprintln!("wslpath-rs: supported prefixes are {supported}");
+            EXIT_INVALID_PREFIX
+        }
+        _ => 1,
+    }
+}
+
+/// Convert all paths read from stdin and write the results to stdout in the same order.
+///
+/// Paths that cannot be converted are reported on stderr without aborting. Returns the exit
+/// status of the first path that failed, or 0 if all paths were converted.
+fn convert_stdin(args: &Args) -> std::io::Result<u8> {
+    let separator = if args.null { b'\0' } else { b'\n' };
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    let mut status = 0;
+    for line in std::io::stdin().lock().split(separator) {
+        let mut line = line?;
+        if !args.null && line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        let result = std::str::from_utf8(&line)
+            .map_err(Into::into)
+            .and_then(|path| convert(path, args));
+        match result {
+            Ok(converted) => {
+                stdout.write_all(converted.as_bytes())?;
+                stdout.write_all(&[separator])?;
+            }
+            Err(error) => {
+                let path = String::from_utf8_lossy(&line);
+                let error_status = report(&path, &*error, args.mode);
+                if status == 0 {
+                    status = error_status;
+                }
+            }
+        }
+    }
+    stdout.flush()?;
+    Ok(status)
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("wslpath-rs: {error}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(path) = &args.path else {
+        return match convert_stdin(&args) {
+            Ok(status) => ExitCode::from(status),
+            Err(error) => {
+                eprintln!("wslpath-rs: {error}");
+                ExitCode::FAILURE
+            }
+        };
+    };
+
+    match convert(path, &args) {
+        Ok(converted) => {
+            println!("{converted}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => ExitCode::from(report(path, &*error, args.mode)),
+    }
+}
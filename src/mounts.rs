@@ -0,0 +1,520 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion of paths with custom mount points.
+
+use crate::{
+    check_windows_path_is_absolute, check_wsl_path_is_absolute, format_windows_path,
+    normalize_windows_path, push_windows_components, push_wsl_components, replace_lookalikes,
+    strip_mount_point, unparsed_drive, windows_to_wsl_with, wsl_to_windows_with, Error, Options,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use typed_path::{UnixPath, WindowsComponent, WindowsPath, WindowsPrefix};
+
+/// An ordered list of custom mount points, each mapping a WSL directory to a Windows drive or
+/// network share (or a directory on one of them).
+///
+/// If several entries match a path, the entry with the longest matching prefix wins. If two
+/// matching prefixes have the same length, the entry that was added first wins.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::MountTable;
+///
+/// let mut table = MountTable::new();
+/// table.push("/c", "C:\\").unwrap();
+/// table.push("/backup", "\\\\server\\backup").unwrap();
+/// assert_eq!(
+///     table.iter().collect::<Vec<_>>(),
+///     vec![("/c", "C:\\"), ("/backup", "\\\\server\\backup")],
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MountTable {
+    /// The `(mount_point, windows_path)` entries in insertion order.
+    entries: Vec<(String, String)>,
+}
+
+impl MountTable {
+    /// Create an empty mount table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entry that mounts the Windows path `windows_path` (e.g. `C:\` or
+    /// `\\server\share`) at the absolute WSL path `mount_point`.
+    ///
+    /// # Errors
+    ///
+    /// If the mount point is not an absolute WSL path, or if the Windows path is neither an
+    /// absolute path nor the root of a share, the entry is not added and the method returns the
+    /// error [`windows_to_wsl`](crate::windows_to_wsl) or [`wsl_to_windows`](crate::wsl_to_windows)
+    /// would return for it (e.g. [`Error::RelativePath`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wslpath_rs::{Error, MountTable};
+    ///
+    /// let mut table = MountTable::new();
+    /// assert_eq!(table.push("projects", "D:\\Projects").unwrap_err(), Error::RelativePath);
+    /// assert_eq!(table.push("/projects", "Projects").unwrap_err(), Error::RelativePath);
+    /// assert_eq!(
+    ///     table.push("/projects", "D:Projects").unwrap_err(),
+    ///     Error::DriveRelativePath { drive: 'D' },
+    /// );
+    /// assert_eq!(table.iter().count(), 0);
+    /// ```
+    pub fn push(
+        &mut self,
+        mount_point: impl Into<String>,
+        windows_path: impl Into<String>,
+    ) -> Result<(), Error> {
+        let mount_point = mount_point.into();
+        let windows_path = windows_path.into();
+        check_wsl_path_is_absolute(UnixPath::new(&mount_point))?;
+        check_mount_target(&windows_path)?;
+        self.entries.push((mount_point, windows_path));
+        Ok(())
+    }
+
+    /// Add an entry that mounts the root of the drive `drive` at the absolute WSL path
+    /// `mount_point`, instead of the default mount point below `/mnt`.
+    ///
+    /// This is a shorthand for pushing an entry for the drive root (e.g. `Z:\`). Like all entries,
+    /// it is consulted in both directions, while other drives keep their default mount points.
+    ///
+    /// # Errors
+    ///
+    /// If the drive letter is not an ASCII letter, the method returns an
+    /// [`Error::InvalidDriveLetter`]. Otherwise, it fails in the same cases as
+    /// [`MountTable::push`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wslpath_rs::{windows_to_wsl_with_table, wsl_to_windows_with_table, MountTable};
+    ///
+    /// let mut table = MountTable::new();
+    /// table.push_drive('Z', "/data").unwrap();
+    ///
+    /// assert_eq!(windows_to_wsl_with_table("Z:\\foo", &table).unwrap(), "/data/foo");
+    /// assert_eq!(windows_to_wsl_with_table("z:\\", &table).unwrap(), "/data");
+    /// assert_eq!(wsl_to_windows_with_table("/data/foo", &table).unwrap(), "Z:\\foo");
+    /// assert_eq!(wsl_to_windows_with_table("/data", &table).unwrap(), "Z:\\");
+    ///
+    /// // Other drives are still mounted below `/mnt`
+    /// assert_eq!(windows_to_wsl_with_table("C:\\foo", &table).unwrap(), "/mnt/c/foo");
+    /// assert_eq!(wsl_to_windows_with_table("/mnt/c/foo", &table).unwrap(), "C:\\foo");
+    ///
+    /// // Directories below an overridden drive can be mounted elsewhere, too
+    /// table.push("/data/archive", "D:\\Archive").unwrap();
+    /// assert_eq!(wsl_to_windows_with_table("/data/archive/2024", &table).unwrap(), "D:\\Archive\\2024");
+    /// assert_eq!(wsl_to_windows_with_table("/data/other", &table).unwrap(), "Z:\\other");
+    /// ```
+    pub fn push_drive(&mut self, drive: char, mount_point: impl Into<String>) -> Result<(), Error> {
+        if !drive.is_ascii_alphabetic() {
+            return Err(Error::InvalidDriveLetter {
+                got: drive.to_string(),
+            });
+        }
+        self.push(mount_point, format!("{}:\\", drive.to_ascii_uppercase()))
+    }
+
+    /// Returns the mount point of the drive `drive` if the table contains an entry for the root of
+    /// the drive (e.g. added with [`MountTable::push_drive`]).
+    ///
+    /// If there is no such entry, the drive is mounted at the default mount point (e.g. `/mnt/c`).
+    /// Drive letters are matched case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wslpath_rs::MountTable;
+    ///
+    /// let mut table = MountTable::new();
+    /// table.push_drive('Z', "/data").unwrap();
+    /// table.push("/projects", "C:\\Users\\user\\Projects").unwrap();
+    ///
+    /// assert_eq!(table.drive_mount_point('Z'), Some("/data"));
+    /// assert_eq!(table.drive_mount_point('z'), Some("/data"));
+    /// assert_eq!(table.drive_mount_point('C'), None);
+    /// ```
+    #[must_use]
+    pub fn drive_mount_point(&self, drive: char) -> Option<&str> {
+        let drive_root = format!("{}:\\", drive.to_ascii_uppercase());
+        self.iter()
+            .find(|(_, windows_path)| normalize_windows_path(windows_path) == drive_root)
+            .map(|(mount_point, _)| mount_point)
+    }
+
+    /// Returns an iterator over the `(mount_point, windows_path)` entries in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(mount_point, windows_path)| (mount_point.as_str(), windows_path.as_str()))
+    }
+}
+
+/// Convert a Windows path to a WSL path, using the mount points from a [`MountTable`].
+///
+/// Paths located below one of the entries' Windows paths are converted to paths below the
+/// corresponding mount point. Windows paths are matched case-insensitively, and all forms of a
+/// drive prefix (e.g. `C:`, `\\?\C:` and `\\.\C:`) match each other. `.` and `..` components are
+/// resolved like [`windows_to_wsl`](crate::windows_to_wsl) does before matching, so they cannot
+/// leave the drive or share. All other paths are converted using
+/// [`windows_to_wsl`](crate::windows_to_wsl).
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`](crate::windows_to_wsl).
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_with_table, MountTable};
+///
+/// let mut table = MountTable::new();
+/// table.push("/c", "C:\\").unwrap();
+/// table.push("/backup", "\\\\server\\backup").unwrap();
+/// table.push("/projects", "C:\\Users\\user\\Projects").unwrap();
+///
+/// assert_eq!(windows_to_wsl_with_table("C:\\Windows", &table).unwrap(), "/c/Windows");
+/// assert_eq!(windows_to_wsl_with_table("c:\\", &table).unwrap(), "/c");
+/// assert_eq!(windows_to_wsl_with_table("\\\\SERVER\\backup\\2024", &table).unwrap(), "/backup/2024");
+///
+/// // The longest matching prefix wins
+/// assert_eq!(
+///     windows_to_wsl_with_table("C:\\Users\\user\\Projects\\foo\\..\\bar", &table).unwrap(),
+///     "/projects/bar",
+/// );
+///
+/// // All forms of a drive prefix match the entry, and `..` components cannot leave it
+/// assert_eq!(windows_to_wsl_with_table("\\\\?\\C:\\Windows", &table).unwrap(), "/c/Windows");
+/// assert_eq!(windows_to_wsl_with_table("\\\\.\\C:\\foo", &table).unwrap(), "/c/foo");
+/// assert_eq!(windows_to_wsl_with_table("\\\\?\\C:\\a/../../x", &table).unwrap(), "/c/x");
+/// assert_eq!(windows_to_wsl_with_table("C:\\Users\\..\\..\\x", &table).unwrap(), "/c/x");
+///
+/// // Other paths use the default mount points
+/// assert_eq!(windows_to_wsl_with_table("D:\\foo", &table).unwrap(), "/mnt/d/foo");
+/// assert_eq!(windows_to_wsl_with_table("\\\\.\\D:\\foo", &table).unwrap(), "/mnt/d/foo");
+/// ```
+pub fn windows_to_wsl_with_table(windows_path: &str, table: &MountTable) -> Result<String, Error> {
+    windows_to_wsl_with_table_with(windows_path, table, &Options::default())
+}
+
+/// Convert a Windows path to a WSL path using custom [`Options`] and the mount points from a
+/// [`MountTable`].
+///
+/// This works like [`windows_to_wsl_with_table`], but paths that do not match any entry are
+/// converted using [`windows_to_wsl_with`]. Paths that match an entry are converted with the
+/// options that apply to the components below the mount point (e.g. [`Options::strict`] or
+/// [`Options::preserve_trailing_separator`]).
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl_with`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_with_table_with, Error, MountTable, Options};
+///
+/// let mut table = MountTable::new();
+/// table.push_drive('Z', "/data").unwrap();
+/// let options = Options::builder().mount_root("/windows").strict(true).build();
+///
+/// assert_eq!(windows_to_wsl_with_table_with("Z:\\foo", &table, &options).unwrap(), "/data/foo");
+/// assert_eq!(windows_to_wsl_with_table_with("C:\\foo", &table, &options).unwrap(), "/windows/c/foo");
+/// assert_eq!(
+///     windows_to_wsl_with_table_with("Z:\\..\\foo", &table, &options).unwrap_err(),
+///     Error::EscapesRoot,
+/// );
+/// ```
+pub fn windows_to_wsl_with_table_with(
+    windows_path: &str,
+    table: &MountTable,
+    options: &Options,
+) -> Result<String, Error> {
+    let replaced = replace_lookalikes(windows_path.as_bytes(), options);
+    let path = WindowsPath::new(&*replaced);
+    check_windows_path_is_absolute(path)?;
+
+    let Some(path) = split_windows_root(path, options)? else {
+        return windows_to_wsl_with(windows_path, options);
+    };
+    let Some((mount_point, names)) = table
+        .iter()
+        .filter_map(|(mount_point, target)| {
+            let target =
+                split_windows_root(WindowsPath::new(target), &Options::default()).ok()??;
+            if !target.root.eq_ignore_ascii_case(&path.root) {
+                return None;
+            }
+            // Like the drive or share, directories on Windows are matched case-insensitively
+            let mut names = wsl_names(&path.rest);
+            let mut length = 0;
+            for target_name in wsl_names(&target.rest) {
+                if !names.next()?.eq_ignore_ascii_case(target_name) {
+                    return None;
+                }
+                length += 1;
+            }
+            Some((length, mount_point, names))
+        })
+        .fold(None, |longest, entry| match longest {
+            Some((length, _, _)) if length >= entry.0 => longest,
+            _ => Some(entry),
+        })
+        .map(|(_, mount_point, names)| (mount_point, names))
+    else {
+        return windows_to_wsl_with(windows_path, options);
+    };
+
+    let mount_point = UnixPath::new(mount_point).normalize();
+    let mut output = Vec::with_capacity(mount_point.as_bytes().len() + path.rest.len() + 1);
+    output.extend_from_slice(mount_point.as_bytes());
+    for name in names {
+        if !output.ends_with(b"/") {
+            output.push(b'/');
+        }
+        output.extend_from_slice(name);
+    }
+    if output.is_empty() {
+        output.push(b'/');
+    }
+    if options.preserve_trailing_separator
+        && windows_path.ends_with(['\\', '/'])
+        && !output.ends_with(b"/")
+    {
+        output.push(b'/');
+    }
+    String::from_utf8(output).map_err(|_| Error::InvalidUtf8)
+}
+
+/// Split an absolute Windows path into the drive or share it is located on (e.g. `C:` or
+/// `\\server\share`) and the WSL path of the remainder below it (e.g. `foo/bar`).
+///
+/// The remainder is converted like [`windows_to_wsl_with`](crate::windows_to_wsl_with) converts
+/// the components after the prefix, so `..` components cannot leave the drive or share. All forms
+/// of drive prefixes (e.g. `C:`, `\\?\C:` and `\\.\C:`) result in the same drive. Returns `None`
+/// for paths that are located neither on a drive nor on a share (e.g. `\\?\Volume{...}`).
+fn split_windows_root(path: &WindowsPath, options: &Options) -> Result<Option<RootedPath>, Error> {
+    let mut components = path.components();
+    let Some(WindowsComponent::Prefix(prefix_component)) = components.next() else {
+        return Ok(None);
+    };
+    let root = match prefix_component.kind() {
+        WindowsPrefix::Disk(drive) | WindowsPrefix::VerbatimDisk(drive) => Vec::from([drive, b':']),
+        WindowsPrefix::Verbatim(name) | WindowsPrefix::DeviceNS(name) => {
+            match unparsed_drive(name).and_then(|drive| u8::try_from(drive).ok()) {
+                Some(drive) if drive.is_ascii_alphabetic() => Vec::from([drive, b':']),
+                _ => return Ok(None),
+            }
+        }
+        WindowsPrefix::UNC(_, b"") | WindowsPrefix::VerbatimUNC(_, b"") => return Ok(None),
+        WindowsPrefix::UNC(hostname, share) | WindowsPrefix::VerbatimUNC(hostname, share) => {
+            [br"\\", hostname, br"\", share].concat()
+        }
+    };
+
+    let mut rest = Vec::new();
+    push_wsl_components(&mut rest, components, options)?;
+    Ok(Some(RootedPath { root, rest }))
+}
+
+/// Check that the Windows path of a mount table entry is absolute or the root of a share (e.g.
+/// `\\server\share`, which has no root directory).
+fn check_mount_target(windows_path: &str) -> Result<(), Error> {
+    let path = WindowsPath::new(windows_path);
+    match check_windows_path_is_absolute(path) {
+        Err(Error::RelativePath) if is_share_root(path) => Ok(()),
+        result => result,
+    }
+}
+
+/// Returns `true` if a Windows path only consists of a drive or share prefix and an optional root
+/// directory (e.g. `D:\` or `\\server\share`).
+fn is_windows_root(path: &WindowsPath) -> bool {
+    let mut components = path.components();
+    matches!(components.next(), Some(WindowsComponent::Prefix(_)))
+        && components.all(|component| component == WindowsComponent::RootDir)
+}
+
+/// Returns `true` if a Windows path is the root of a share (e.g. `\\server\share`).
+fn is_share_root(path: &WindowsPath) -> bool {
+    let is_share = path.components().next().is_some_and(|component| {
+        matches!(
+            component,
+            WindowsComponent::Prefix(prefix_component)
+                if matches!(
+                    prefix_component.kind(),
+                    WindowsPrefix::UNC(_, share) | WindowsPrefix::VerbatimUNC(_, share)
+                        if !share.is_empty()
+                )
+        )
+    });
+    is_share && is_windows_root(path)
+}
+
+/// A Windows path split by [`split_windows_root`].
+struct RootedPath {
+    /// The drive or share (e.g. `C:` or `\\server\share`).
+    root: Vec<u8>,
+    /// The WSL path of the remainder below the drive or share (e.g. `foo/bar`).
+    rest: Vec<u8>,
+}
+
+/// Returns the names of the components of a relative WSL path (e.g. `foo` and `bar` for
+/// `foo/bar`).
+fn wsl_names(path: &[u8]) -> impl Iterator<Item = &[u8]> {
+    path.split(|&byte| byte == b'/')
+        .filter(|name| !name.is_empty())
+}
+
+/// Convert a WSL path to a Windows path, using the mount points from a [`MountTable`].
+///
+/// Paths located below one of the entries' mount points are converted to paths below the
+/// corresponding Windows path. All other paths are converted using
+/// [`wsl_to_windows`](crate::wsl_to_windows).
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`](crate::wsl_to_windows).
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_with_table, wsl_to_windows_with_table, Error, MountTable};
+///
+/// let mut table = MountTable::new();
+/// table.push("/c", "C:\\").unwrap();
+/// table.push("/backup", "\\\\server\\backup").unwrap();
+/// table.push("/c/projects", "D:\\Projects").unwrap();
+///
+/// assert_eq!(wsl_to_windows_with_table("/c/Windows", &table).unwrap(), "C:\\Windows");
+/// assert_eq!(wsl_to_windows_with_table("/c", &table).unwrap(), "C:\\");
+/// assert_eq!(wsl_to_windows_with_table("/backup/2024/", &table).unwrap(), "\\\\server\\backup\\2024");
+/// assert_eq!(wsl_to_windows_with_table("/backup", &table).unwrap(), "\\\\server\\backup\\");
+///
+/// // The longest matching prefix wins
+/// assert_eq!(wsl_to_windows_with_table("/c/projects/foo", &table).unwrap(), "D:\\Projects\\foo");
+///
+/// // Only the roots of drives and shares end with a separator
+/// assert_eq!(wsl_to_windows_with_table("/c/projects", &table).unwrap(), "D:\\Projects");
+/// assert_eq!(wsl_to_windows_with_table("/c/projects/", &table).unwrap(), "D:\\Projects");
+/// assert_eq!(windows_to_wsl_with_table("D:\\Projects", &table).unwrap(), "/c/projects");
+///
+/// // Other paths use the default mount points
+/// assert_eq!(wsl_to_windows_with_table("/mnt/d/foo", &table).unwrap(), "D:\\foo");
+/// assert_eq!(
+///     wsl_to_windows_with_table("/home/user", &table).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/home".to_string() },
+/// );
+/// ```
+pub fn wsl_to_windows_with_table(wsl_path: &str, table: &MountTable) -> Result<String, Error> {
+    wsl_to_windows_with_table_with(wsl_path, table, &Options::default())
+}
+
+/// Convert a WSL path to a Windows path using custom [`Options`] and the mount points from a
+/// [`MountTable`].
+///
+/// This works like [`wsl_to_windows_with_table`], but paths that do not match any entry are
+/// converted using [`wsl_to_windows_with`]. Paths that match an entry are converted with the
+/// options that apply to the components below the mount point and to the output (e.g.
+/// [`Options::normalize`], [`Options::strict`], [`Options::validate_windows_names`] or
+/// [`Options::separator`]).
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows_with`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_windows_with_table_with, Error, MountTable, Options, Separator};
+///
+/// let mut table = MountTable::new();
+/// table.push_drive('Z', "/data").unwrap();
+/// let options = Options::builder()
+///     .mount_root("/windows")
+///     .separator(Separator::ForwardSlash)
+///     .validate_windows_names(true)
+///     .build();
+///
+/// assert_eq!(wsl_to_windows_with_table_with("/data/foo", &table, &options).unwrap(), "Z:/foo");
+/// assert_eq!(wsl_to_windows_with_table_with("/windows/c/foo", &table, &options).unwrap(), "C:/foo");
+/// assert_eq!(
+///     wsl_to_windows_with_table_with("/data/NUL", &table, &options).unwrap_err(),
+///     Error::ReservedWindowsName { component: "NUL".to_string() },
+/// );
+/// assert_eq!(
+///     wsl_to_windows_with_table_with("/mnt/c/foo", &table, &options).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/mnt".to_string() },
+/// );
+///
+/// let options = Options::builder().normalize(false).build();
+/// assert_eq!(wsl_to_windows_with_table_with("/data/a/../b", &table, &options).unwrap(), "Z:\\a\\..\\b");
+///
+/// let options = Options::builder().strict(true).build();
+/// assert_eq!(wsl_to_windows_with_table_with("/data/a/../b", &table, &options).unwrap(), "Z:\\b");
+/// assert_eq!(
+///     wsl_to_windows_with_table_with("/data/../../b", &table, &options).unwrap_err(),
+///     Error::EscapesRoot,
+/// );
+/// ```
+pub fn wsl_to_windows_with_table_with(
+    wsl_path: &str,
+    table: &MountTable,
+    options: &Options,
+) -> Result<String, Error> {
+    let replaced = replace_lookalikes(wsl_path.as_bytes(), options);
+    let path = UnixPath::new(&*replaced);
+    check_wsl_path_is_absolute(path)?;
+
+    let mut components = path.components();
+    components.next();
+    let Some((target, rest)) = table
+        .iter()
+        .filter_map(|(mount_point, target)| {
+            let mount_point = UnixPath::new(mount_point).normalize();
+            let mount_point = core::str::from_utf8(mount_point.as_bytes()).ok()?;
+            let (rest, _) = strip_mount_point(components.clone(), mount_point).ok()?;
+            let length = UnixPath::new(mount_point).components().count();
+            Some((length, target, rest))
+        })
+        .fold(None, |longest, entry| match longest {
+            Some((length, _, _)) if length >= entry.0 => longest,
+            _ => Some(entry),
+        })
+        .map(|(_, target, rest)| (target, rest))
+    else {
+        return wsl_to_windows_with(wsl_path, options);
+    };
+
+    // Only the root of a drive or share keeps its separator (e.g. `D:\`), like in the conversion
+    // to WSL paths
+    let is_root = is_windows_root(WindowsPath::new(target));
+    let target = target.trim_end_matches(['\\', '/']);
+    let mut output = Vec::with_capacity(target.len() + path.as_bytes().len() + 1);
+    output.extend_from_slice(target.as_bytes());
+    push_windows_components(&mut output, rest, options)?;
+    if (is_root && output.len() == target.len())
+        || (options.preserve_trailing_separator
+            && wsl_path.ends_with('/')
+            && !output.ends_with(b"\\"))
+    {
+        output.push(b'\\');
+    }
+    format_windows_path(&mut output, options);
+    String::from_utf8(output).map_err(|_| Error::InvalidUtf8)
+}
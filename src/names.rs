@@ -0,0 +1,106 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Validation of Windows file names.
+
+use crate::{Error, Options};
+use alloc::string::{String, ToString};
+
+/// Characters that must not appear in Windows file names (in addition to control characters).
+const RESERVED_CHARACTERS: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
+
+/// Device names that are reserved on Windows, regardless of case and extension.
+const RESERVED_NAMES: [&str; 4] = ["CON", "PRN", "AUX", "NUL"];
+
+/// Prefixes of numbered device names that are reserved on Windows (e.g. `COM1` or `LPT9`).
+const RESERVED_NUMBERED_NAMES: [&str; 2] = ["COM", "LPT"];
+
+/// Returns `true` if the file name refers to a reserved device on Windows (e.g. `CON` or
+/// `lpt3.txt`).
+fn is_reserved_name(name: &str) -> bool {
+    // Windows ignores the extension and trailing spaces before it (e.g. `con .txt`)
+    let stem = name
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(' ');
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        return true;
+    }
+
+    let (Some(prefix), Some(number)) = (stem.get(..3), stem.get(3..)) else {
+        return false;
+    };
+    RESERVED_NUMBERED_NAMES
+        .iter()
+        .any(|reserved| prefix.eq_ignore_ascii_case(reserved))
+        && matches!(
+            number,
+            "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" | "¹" | "²" | "³"
+        )
+}
+
+/// Check that a component of a WSL path can be used in a Windows path, i.e. that it does not
+/// contain a backslash and, if [`Options::validate_windows_names`] is enabled, that it is a valid
+/// Windows file name.
+///
+/// Components that are not valid UTF-8 are checked (and reported) with invalid sequences replaced
+/// by `U+FFFD REPLACEMENT CHARACTER`.
+pub(crate) fn check_windows_component(name: &[u8], options: &Options) -> Result<(), Error> {
+    let name = String::from_utf8_lossy(name);
+    check_no_backslash(&name)?;
+    if options.validate_windows_names {
+        validate_windows_name(&name)?;
+    }
+    Ok(())
+}
+
+/// Check that a path component does not contain a backslash.
+///
+/// Unlike [`validate_windows_name`], this check is always applied, because Windows would treat
+/// the backslash as separator and silently split the component in two.
+pub(crate) fn check_no_backslash(name: &str) -> Result<(), Error> {
+    if name.contains('\\') {
+        return Err(Error::InvalidWindowsComponent {
+            component: name.to_string(),
+            ch: '\\',
+        });
+    }
+    Ok(())
+}
+
+/// Check that a path component can be used as a file name on Windows.
+pub(crate) fn validate_windows_name(name: &str) -> Result<(), Error> {
+    if let Some(ch) = name
+        .chars()
+        .find(|ch| ch.is_ascii_control() || RESERVED_CHARACTERS.contains(ch))
+    {
+        return Err(Error::InvalidWindowsComponent {
+            component: name.to_string(),
+            ch,
+        });
+    }
+
+    if is_reserved_name(name) {
+        return Err(Error::ReservedWindowsName {
+            component: name.to_string(),
+        });
+    }
+
+    // Windows silently strips trailing dots and spaces, so e.g. `foo.` would refer to `foo`
+    if name.ends_with(['.', ' ']) {
+        return Err(Error::AmbiguousWindowsName {
+            component: name.to_string(),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion of native [`std::path`] paths.
+
+use crate::Error;
+use alloc::string::String;
+use std::io;
+
+/// Convert a native Windows path to a WSL path.
+///
+/// The path is converted losslessly via [`windows_to_wsl_bytes`](crate::windows_to_wsl_bytes).
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`](crate::windows_to_wsl). If the
+/// converted path is not valid UTF-8, [`Error::InvalidUtf8`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use wslpath_rs::windows_path_to_wsl;
+///
+/// assert_eq!(windows_path_to_wsl(Path::new("C:\\Windows")).unwrap(), "/mnt/c/Windows");
+/// ```
+#[cfg(windows)]
+pub fn windows_path_to_wsl(path: &std::path::Path) -> Result<String, Error> {
+    let converted = crate::windows_to_wsl_bytes(path.as_os_str().as_encoded_bytes())?;
+    String::from_utf8(converted).map_err(|_| Error::InvalidUtf8)
+}
+
+/// Convert a native WSL path to a Windows path.
+///
+/// The path is converted losslessly via [`wsl_to_windows_bytes`](crate::wsl_to_windows_bytes).
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`](crate::wsl_to_windows). If the
+/// converted path is not valid UTF-8, [`Error::InvalidUtf8`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use std::os::unix::ffi::OsStrExt;
+/// use std::path::Path;
+/// use wslpath_rs::{wsl_path_to_windows, Error};
+///
+/// assert_eq!(wsl_path_to_windows(Path::new("/mnt/c/Windows")).unwrap(), "C:\\Windows");
+///
+/// let non_utf8_path = Path::new(OsStr::from_bytes(b"/mnt/c/\xff.txt"));
+/// assert_eq!(wsl_path_to_windows(non_utf8_path).unwrap_err(), Error::InvalidUtf8);
+/// ```
+#[cfg(unix)]
+pub fn wsl_path_to_windows(path: &std::path::Path) -> Result<String, Error> {
+    let converted = crate::wsl_to_windows_bytes(path.as_os_str().as_encoded_bytes())?;
+    String::from_utf8(converted).map_err(|_| Error::InvalidUtf8)
+}
+
+/// Resolve a Windows path via the filesystem and convert it to a WSL path.
+///
+/// The path is canonicalized with [`std::fs::canonicalize`] first, so that symbolic links and
+/// `..` components are resolved against the actual filesystem. The `\\?\` prefix that is added by
+/// canonicalization is stripped before the path is converted with
+/// [`windows_to_wsl`](crate::windows_to_wsl).
+///
+/// Note that this accesses the filesystem, so it is only available on Windows.
+///
+/// # Errors
+///
+/// If the path does not exist or cannot be canonicalized, the error from
+/// [`std::fs::canonicalize`] is returned. If the canonical path cannot be converted, an error of
+/// kind [`io::ErrorKind::InvalidInput`] that wraps the [`Error`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::windows_to_wsl_canonicalized;
+///
+/// assert_eq!(
+///     windows_to_wsl_canonicalized("C:\\Windows\\System32\\..").unwrap(),
+///     "/mnt/c/Windows",
+/// );
+///
+/// let error = windows_to_wsl_canonicalized("C:\\does\\not\\exist").unwrap_err();
+/// assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+/// ```
+#[cfg(windows)]
+pub fn windows_to_wsl_canonicalized(path: &str) -> io::Result<String> {
+    let canonical = std::fs::canonicalize(path)?;
+    let canonical = canonical
+        .to_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, Error::InvalidUtf8))?;
+    let converted = match canonical.strip_prefix(r"\\?\") {
+        // "\\?\UNC\server\share" -> "\\server\share"
+        Some(path) => match path.strip_prefix(r"UNC\") {
+            Some(path) => crate::windows_to_wsl(&alloc::format!(r"\\{path}")),
+            None => crate::windows_to_wsl(path),
+        },
+        None => crate::windows_to_wsl(canonical),
+    };
+    converted.map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))
+}
+
+/// Resolve a WSL path via the filesystem and convert it to a Windows path.
+///
+/// The path is canonicalized with [`std::fs::canonicalize`] first, so that symbolic links and
+/// `..` components are resolved against the actual filesystem, and then converted with
+/// [`wsl_to_windows`](crate::wsl_to_windows).
+///
+/// Note that this accesses the filesystem, so it is only available on Unix.
+///
+/// # Errors
+///
+/// If the path does not exist or cannot be canonicalized, the error from
+/// [`std::fs::canonicalize`] is returned. If the canonical path cannot be converted (e.g. because
+/// it is not located on a Windows drive), an error of kind [`io::ErrorKind::InvalidInput`] that
+/// wraps the [`Error`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use wslpath_rs::wsl_to_windows_canonicalized;
+///
+/// let error = wsl_to_windows_canonicalized("/does/not/exist").unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::NotFound);
+///
+/// // The root directory exists, but is not located on a Windows drive
+/// let error = wsl_to_windows_canonicalized("/").unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::InvalidInput);
+/// ```
+#[cfg(unix)]
+pub fn wsl_to_windows_canonicalized(path: &str) -> io::Result<String> {
+    let canonical = std::fs::canonicalize(path)?;
+    wsl_path_to_windows(&canonical)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))
+}
@@ -0,0 +1,634 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Options to customize the path conversion.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Controls whether the `\\?\` prefix is added to converted Windows paths.
+///
+/// Windows APIs require this prefix to access paths that are longer than `MAX_PATH` (260
+/// characters, including the terminating null character).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VerbatimMode {
+    /// Never add the prefix.
+    Never,
+    /// Always add the prefix (e.g. `\\?\C:\Windows`).
+    Always,
+    /// Only add the prefix if the path does not fit into `MAX_PATH`, i.e. if it is longer than 259
+    /// characters.
+    Auto,
+}
+
+/// The separator used in converted Windows paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Separator {
+    /// Use backslashes (e.g. `C:\Windows\System32`).
+    Backslash,
+    /// Use forward slashes (e.g. `C:/Windows/System32`), which most Windows APIs accept too.
+    ForwardSlash,
+}
+
+/// The case of drive letters in converted paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DriveCase {
+    /// Use a lowercase drive letter (e.g. `c`).
+    Lower,
+    /// Use an uppercase drive letter (e.g. `C`).
+    Upper,
+    /// Keep the drive letter as it appears in the input path.
+    Preserve,
+}
+
+impl DriveCase {
+    /// Apply the case to a drive letter.
+    pub(crate) fn apply(self, drive: char) -> char {
+        match self {
+            Self::Lower => drive.to_ascii_lowercase(),
+            Self::Upper => drive.to_ascii_uppercase(),
+            Self::Preserve => drive,
+        }
+    }
+}
+
+/// Controls how network paths on unknown hosts are handled when converting Windows paths.
+///
+/// Unknown hosts are hosts that are neither WSL hosts (e.g. `wsl.localhost`), nor configured
+/// in [`Options::admin_share_hosts`], nor mounted below [`Options::network_mount`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnknownHostPolicy {
+    /// Fail with [`Error::InvalidPrefix`](crate::Error::InvalidPrefix).
+    Error,
+    /// Return the original path unchanged, so that the caller can handle it.
+    Passthrough,
+}
+
+/// The version of WSL that paths are converted for.
+///
+/// WSL 1 and WSL 2 currently mount Windows drives at the same locations, so the version only
+/// serves as a hint that is passed through to [detailed conversions](crate::Conversion). Future
+/// versions of this crate may use it to adjust defaults to version-specific mount conventions.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::WslVersion;
+///
+/// assert_eq!(WslVersion::Wsl1.default_mount_root(), "/mnt");
+/// assert_eq!(WslVersion::Wsl2.default_mount_root(), "/mnt");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum WslVersion {
+    /// WSL 1, which translates Linux system calls in the Windows kernel.
+    Wsl1,
+    /// WSL 2, which runs a Linux kernel in a lightweight virtual machine.
+    Wsl2,
+}
+
+impl WslVersion {
+    /// Returns the directory where this version of WSL mounts Windows drives by default.
+    #[must_use]
+    pub const fn default_mount_root(self) -> &'static str {
+        match self {
+            Self::Wsl1 | Self::Wsl2 => "/mnt",
+        }
+    }
+}
+
+/// Options that control how paths are converted.
+///
+/// The default options reproduce the behavior of WSL's default configuration.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::Options;
+///
+/// let mut options = Options::default();
+/// assert_eq!(options.mount_root, "/mnt");
+///
+/// options.mount_root = "/windows".to_string();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct Options {
+    /// The directory where Windows drives are mounted inside WSL (`automount.root` in
+    /// `/etc/wsl.conf`).
+    ///
+    /// Leading and trailing slashes are optional, so `/` (or the empty string) mounts drives
+    /// directly under the root directory (e.g. `C:\` becomes `/c`) like Git Bash and MSYS2 do.
+    /// Cygwin uses `/cygdrive` (see [`windows_to_cygwin`](crate::windows_to_cygwin)).
+    pub mount_root: String,
+    /// Whether Windows drives are mounted inside WSL at all (`automount.enabled` in
+    /// `/etc/wsl.conf`).
+    ///
+    /// If disabled, converting drive paths fails with
+    /// [`Error::AutomountDisabled`](crate::Error::AutomountDisabled).
+    pub automount_enabled: bool,
+    /// Whether to reject paths that leave the drive or mount they started under (e.g.
+    /// `C:\..\Windows` or `/mnt/c/../../etc`).
+    ///
+    /// If enabled, such paths fail with [`Error::EscapesRoot`](crate::Error::EscapesRoot).
+    /// Otherwise, they are normalized leniently. Disabled by default.
+    pub strict: bool,
+    /// Whether to normalize the converted path by resolving `.` and `..` components.
+    ///
+    /// Resolving `..` components without consulting the filesystem may lead to wrong results if
+    /// the path contains symbolic links, so this can be disabled to keep the path structure
+    /// unchanged. Enabled by default.
+    pub normalize: bool,
+    /// Whether to keep a trailing separator of the input path (e.g. `C:\Windows\` becomes
+    /// `/mnt/c/Windows/` instead of `/mnt/c/Windows`).
+    ///
+    /// Some tools use a trailing separator to denote a directory. Disabled by default.
+    pub preserve_trailing_separator: bool,
+    /// Whether to add the `\\?\` prefix to converted Windows paths.
+    ///
+    /// Defaults to [`VerbatimMode::Never`].
+    pub verbatim: VerbatimMode,
+    /// The separator used in converted Windows paths.
+    ///
+    /// Defaults to [`Separator::Backslash`]. Paths with the `\\?\` prefix always use backslashes,
+    /// because Windows does not accept forward slashes in them.
+    pub separator: Separator,
+    /// The case of the drive letter in converted WSL paths (e.g. `/mnt/c`).
+    ///
+    /// Defaults to [`DriveCase::Lower`], which matches the mount points created by WSL.
+    pub drive_case: DriveCase,
+    /// The case of the drive letter in converted Windows paths (e.g. `C:\`).
+    ///
+    /// Defaults to [`DriveCase::Upper`], which matches Windows conventions.
+    pub windows_drive_case: DriveCase,
+    /// Hosts whose administrative drive shares (e.g. `\\localhost\C$\Windows`) are converted like
+    /// the corresponding drive (e.g. `/mnt/c/Windows`).
+    ///
+    /// Whether a host name refers to the local machine cannot be determined from the path alone,
+    /// so all listed hosts are assumed to be the local machine (e.g. `localhost` or the computer
+    /// name). Hosts are matched case-insensitively. Empty by default, so administrative shares
+    /// fail with [`Error::InvalidPrefix`](crate::Error::InvalidPrefix).
+    pub admin_share_hosts: Vec<String>,
+    /// The directory where network shares are mounted inside WSL.
+    ///
+    /// If set (e.g. to `/mnt/network`), UNC paths like `\\server\share\dir` are converted to
+    /// `/mnt/network/server/share/dir` and back. Paths to the filesystem of a WSL distribution and
+    /// [administrative shares](Options::admin_share_hosts) take precedence. `None` by default, so
+    /// network paths fail with [`Error::InvalidPrefix`](crate::Error::InvalidPrefix).
+    pub network_mount: Option<String>,
+    /// Whether to reject WSL paths that cannot be represented on Windows when converting them to
+    /// Windows paths.
+    ///
+    /// If enabled, path components that contain characters which are not allowed in Windows file
+    /// names (`<`, `>`, `:`, `"`, `|`, `?`, `*` and control characters) fail with
+    /// [`Error::InvalidWindowsComponent`](crate::Error::InvalidWindowsComponent), and reserved
+    /// device names (`CON`, `PRN`, `AUX`, `NUL`, `COM1`-`COM9` and `LPT1`-`LPT9`, with or without
+    /// extension) fail with [`Error::ReservedWindowsName`](crate::Error::ReservedWindowsName).
+    /// Components ending with a dot or space fail with
+    /// [`Error::AmbiguousWindowsName`](crate::Error::AmbiguousWindowsName), because Windows strips
+    /// them silently (e.g. `foo.` refers to the same file as `foo`). Disabled by default, in which
+    /// case such components are converted unchanged.
+    pub validate_windows_names: bool,
+    /// Mount points of volumes that have no drive letter, keyed by volume GUID.
+    ///
+    /// Paths like `\\?\Volume{12345678-90ab-cdef-1234-567890abcdef}\folder` are converted to
+    /// paths below the mount point of the volume with the matching GUID. GUIDs are matched
+    /// case-insensitively, with or without curly braces. Volumes without a mount point fail with
+    /// [`Error::InvalidPrefix`](crate::Error::InvalidPrefix). Empty by default.
+    pub volume_mounts: BTreeMap<String, String>,
+    /// Whether only lowercase drive letters are accepted in mount points of WSL paths.
+    ///
+    /// WSL always mounts drives at lowercase directories (e.g. `/mnt/c`), so `/mnt/C` is most
+    /// likely a different directory. By default, uppercase drive letters are accepted leniently
+    /// and `/mnt/C/Windows` is converted to `C:\Windows`. If enabled, such paths fail with
+    /// [`Error::InvalidPrefix`](crate::Error::InvalidPrefix).
+    pub case_sensitive_mount: bool,
+    /// Whether characters that look like colons or separators are treated like their ASCII
+    /// equivalents.
+    ///
+    /// Paths copied from GUI applications sometimes contain e.g. a fullwidth colon (`：`) or
+    /// fullwidth solidus (`／`). If enabled, these characters are replaced before the path is
+    /// parsed, so that `C：\Windows` is converted like `C:\Windows`. Disabled by default, in
+    /// which case they are treated as part of file names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wslpath_rs::{windows_to_wsl_with, wsl_to_windows_with, Error, Options};
+    ///
+    /// let mut options = Options::default();
+    /// assert_eq!(windows_to_wsl_with("C：\\Windows", &options).unwrap_err(), Error::RelativePath);
+    ///
+    /// options.lenient_unicode = true;
+    /// assert_eq!(windows_to_wsl_with("C：\\Windows", &options).unwrap(), "/mnt/c/Windows");
+    /// assert_eq!(windows_to_wsl_with("C：＼Windows＼System32", &options).unwrap(), "/mnt/c/Windows/System32");
+    /// assert_eq!(wsl_to_windows_with("／mnt／c／Windows", &options).unwrap(), "C:\\Windows");
+    /// ```
+    pub lenient_unicode: bool,
+    /// The version of WSL that paths are converted for, if known.
+    ///
+    /// The version does not change how paths are converted, but is reported in the
+    /// [`Conversion`](crate::Conversion) returned by detailed conversions like
+    /// [`windows_to_wsl_detailed_with`](crate::windows_to_wsl_detailed_with). Use
+    /// [`Options::for_wsl_version`] to also apply the version's defaults. `None` by default.
+    pub wsl_version: Option<WslVersion>,
+    /// The name of the WSL distribution that converted paths are used in, if known.
+    ///
+    /// If set, paths to the filesystem of a WSL distribution (e.g. `\\wsl.localhost\Debian\home`)
+    /// are only converted if they refer to this distribution, because the filesystems of other
+    /// distributions are not accessible from inside WSL. Paths to other distributions fail with
+    /// [`Error::ForeignDistro`](crate::Error::ForeignDistro). Distribution names are matched
+    /// case-insensitively. `None` by default, so all paths to distributions are assumed to refer
+    /// to the current one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wslpath_rs::{windows_to_wsl_with, Error, Options};
+    ///
+    /// let mut options = Options::default();
+    /// assert_eq!(windows_to_wsl_with("\\\\wsl$\\Debian\\home\\user", &options).unwrap(), "/home/user");
+    ///
+    /// options.current_distro = Some("Ubuntu".to_string());
+    /// assert_eq!(windows_to_wsl_with("\\\\wsl$\\Ubuntu\\home\\user", &options).unwrap(), "/home/user");
+    /// assert_eq!(windows_to_wsl_with("\\\\wsl$\\ubuntu\\home\\user", &options).unwrap(), "/home/user");
+    /// assert_eq!(
+    ///     windows_to_wsl_with("\\\\wsl.localhost\\Debian\\home\\user", &options).unwrap_err(),
+    ///     Error::ForeignDistro { distro: "Debian".to_string() },
+    /// );
+    ///
+    /// // Paths on Windows drives are not affected
+    /// assert_eq!(windows_to_wsl_with("C:\\Windows", &options).unwrap(), "/mnt/c/Windows");
+    /// ```
+    pub current_distro: Option<String>,
+    /// How network paths on unknown hosts (e.g. `\\server\share`) are handled by
+    /// [`windows_to_wsl_with`](crate::windows_to_wsl_with).
+    ///
+    /// With [`UnknownHostPolicy::Passthrough`], such paths are returned unchanged instead of
+    /// failing, so that a lenient frontend can convert what it can and leave the rest alone.
+    /// Detailed conversions always fail, because the path does not refer to a location inside
+    /// WSL. [`UnknownHostPolicy::Error`] by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wslpath_rs::{windows_to_wsl_with, Error, Options, UnknownHostPolicy};
+    ///
+    /// let mut options = Options::default();
+    /// assert_eq!(
+    ///     windows_to_wsl_with("\\\\server\\share\\foo", &options).unwrap_err(),
+    ///     Error::InvalidPrefix { prefix: "\\\\server\\share".to_string() },
+    /// );
+    ///
+    /// options.on_unknown_host = UnknownHostPolicy::Passthrough;
+    /// assert_eq!(windows_to_wsl_with("\\\\server\\share\\foo", &options).unwrap(), "\\\\server\\share\\foo");
+    /// assert_eq!(windows_to_wsl_with("//server/share/./foo", &options).unwrap(), "//server/share/./foo");
+    /// assert_eq!(
+    ///     windows_to_wsl_with("\\\\?\\UNC\\server\\share\\foo", &options).unwrap(),
+    ///     "\\\\?\\UNC\\server\\share\\foo",
+    /// );
+    ///
+    /// // Known hosts are still converted and other errors are still reported
+    /// assert_eq!(windows_to_wsl_with("\\\\wsl$\\Ubuntu\\home", &options).unwrap(), "/home");
+    /// assert_eq!(windows_to_wsl_with("C:\\Windows", &options).unwrap(), "/mnt/c/Windows");
+    /// assert_eq!(
+    ///     windows_to_wsl_with("\\\\?\\GLOBALROOT\\Device", &options).unwrap_err(),
+    ///     Error::InvalidPrefix { prefix: "\\\\?\\GLOBALROOT".to_string() },
+    /// );
+    /// assert_eq!(windows_to_wsl_with("foo", &options).unwrap_err(), Error::RelativePath);
+    /// ```
+    pub on_unknown_host: UnknownHostPolicy,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            mount_root: "/mnt".to_string(),
+            automount_enabled: true,
+            strict: false,
+            normalize: true,
+            preserve_trailing_separator: false,
+            verbatim: VerbatimMode::Never,
+            separator: Separator::Backslash,
+            drive_case: DriveCase::Lower,
+            windows_drive_case: DriveCase::Upper,
+            admin_share_hosts: Vec::new(),
+            network_mount: None,
+            validate_windows_names: false,
+            volume_mounts: BTreeMap::new(),
+            case_sensitive_mount: false,
+            lenient_unicode: false,
+            wsl_version: None,
+            current_distro: None,
+            on_unknown_host: UnknownHostPolicy::Error,
+        }
+    }
+}
+
+impl Options {
+    /// Returns a builder to configure options with chained method calls.
+    ///
+    /// See [`OptionsBuilder`] for details.
+    #[must_use]
+    pub fn builder() -> OptionsBuilder {
+        OptionsBuilder::default()
+    }
+
+    /// Returns the default options for a specific version of WSL.
+    ///
+    /// The [`Options::wsl_version`] hint is set and the defaults are adjusted to the version (see
+    /// [`WslVersion::default_mount_root`]). All other options are the same as in
+    /// [`Options::default`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wslpath_rs::{windows_to_wsl_with, Options, WslVersion};
+    ///
+    /// for version in [WslVersion::Wsl1, WslVersion::Wsl2] {
+    ///     let options = Options::for_wsl_version(version);
+    ///     assert_eq!(options.mount_root, "/mnt");
+    ///     assert_eq!(options.wsl_version, Some(version));
+    ///     assert_eq!(windows_to_wsl_with("C:\\Windows", &options).unwrap(), "/mnt/c/Windows");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn for_wsl_version(version: WslVersion) -> Self {
+        Self {
+            mount_root: version.default_mount_root().to_string(),
+            wsl_version: Some(version),
+            ..Self::default()
+        }
+    }
+
+    /// Read the options from a `wsl.conf` file (usually located at `/etc/wsl.conf`).
+    ///
+    /// See [`Options::from_wsl_conf_str`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    #[cfg(feature = "std")]
+    pub fn from_wsl_conf<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(Self::from_wsl_conf_str(&contents))
+    }
+
+    /// Parse the options from the contents of a `wsl.conf` file.
+    ///
+    /// Only the `root` and `enabled` keys of the `[automount]` section are taken into account.
+    /// Missing keys, unknown keys and invalid values are ignored and the default options are used
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wslpath_rs::Options;
+    ///
+    /// let options = Options::from_wsl_conf_str(r#"
+    /// # Enable extra metadata options by default
+    /// [automount]
+    /// enabled = true
+    /// root = /windows/
+    /// options = "metadata,umask=22,fmask=11"
+    ///
+    /// [network]
+    /// generateHosts = false
+    /// "#);
+    /// assert_eq!(options.mount_root, "/windows/");
+    /// assert!(options.automount_enabled);
+    ///
+    /// let options = Options::from_wsl_conf_str("[automount]\nenabled = false\n");
+    /// assert_eq!(options.mount_root, "/mnt");
+    /// assert!(!options.automount_enabled);
+    /// ```
+    #[must_use]
+    pub fn from_wsl_conf_str(contents: &str) -> Self {
+        let mut options = Self::default();
+        let mut in_automount_section = false;
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                in_automount_section = section.trim().eq_ignore_ascii_case("automount");
+                continue;
+            }
+
+            if !in_automount_section {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            match key.trim() {
+                "root" => options.mount_root = value.to_string(),
+                "enabled" => {
+                    if value.eq_ignore_ascii_case("true") {
+                        options.automount_enabled = true;
+                    } else if value.eq_ignore_ascii_case("false") {
+                        options.automount_enabled = false;
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        options
+    }
+}
+
+/// A builder for [`Options`].
+///
+/// The builder starts with the [default options](Options::default), so that only the options
+/// that differ from the defaults need to be set.
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_wsl_with, DriveCase, Options, Separator};
+///
+/// let options = Options::builder()
+///     .mount_root("/windows")
+///     .drive_case(DriveCase::Preserve)
+///     .separator(Separator::ForwardSlash)
+///     .build();
+/// assert_eq!(options.mount_root, "/windows");
+/// assert_eq!(windows_to_wsl_with("D:\\foo", &options).unwrap(), "/windows/D/foo");
+///
+/// assert_eq!(Options::builder().build(), Options::default());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OptionsBuilder {
+    /// The options that are being built.
+    options: Options,
+}
+
+impl OptionsBuilder {
+    /// Set the directory where Windows drives are mounted (see [`Options::mount_root`]).
+    #[must_use]
+    pub fn mount_root(mut self, mount_root: impl Into<String>) -> Self {
+        self.options.mount_root = mount_root.into();
+        self
+    }
+
+    /// Enable or disable the conversion of drive paths (see [`Options::automount_enabled`]).
+    #[must_use]
+    pub fn automount_enabled(mut self, automount_enabled: bool) -> Self {
+        self.options.automount_enabled = automount_enabled;
+        self
+    }
+
+    /// Enable or disable strict mode (see [`Options::strict`]).
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.options.strict = strict;
+        self
+    }
+
+    /// Enable or disable the normalization of converted paths (see [`Options::normalize`]).
+    #[must_use]
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.options.normalize = normalize;
+        self
+    }
+
+    /// Set whether trailing separators are preserved (see
+    /// [`Options::preserve_trailing_separator`]).
+    #[must_use]
+    pub fn preserve_trailing_separator(mut self, preserve_trailing_separator: bool) -> Self {
+        self.options.preserve_trailing_separator = preserve_trailing_separator;
+        self
+    }
+
+    /// Set when the `\\?\` prefix is added to Windows paths (see [`Options::verbatim`]).
+    #[must_use]
+    pub fn verbatim(mut self, verbatim: VerbatimMode) -> Self {
+        self.options.verbatim = verbatim;
+        self
+    }
+
+    /// Set the separator used in Windows paths (see [`Options::separator`]).
+    #[must_use]
+    pub fn separator(mut self, separator: Separator) -> Self {
+        self.options.separator = separator;
+        self
+    }
+
+    /// Set the case of drive letters in WSL paths (see [`Options::drive_case`]).
+    #[must_use]
+    pub fn drive_case(mut self, drive_case: DriveCase) -> Self {
+        self.options.drive_case = drive_case;
+        self
+    }
+
+    /// Set the case of drive letters in Windows paths (see [`Options::windows_drive_case`]).
+    #[must_use]
+    pub fn windows_drive_case(mut self, windows_drive_case: DriveCase) -> Self {
+        self.options.windows_drive_case = windows_drive_case;
+        self
+    }
+
+    /// Add a host whose administrative shares are converted to drive paths (see
+    /// [`Options::admin_share_hosts`]).
+    #[must_use]
+    pub fn admin_share_host(mut self, hostname: impl Into<String>) -> Self {
+        self.options.admin_share_hosts.push(hostname.into());
+        self
+    }
+
+    /// Set the directory where network shares are mounted (see [`Options::network_mount`]).
+    #[must_use]
+    pub fn network_mount(mut self, network_mount: impl Into<String>) -> Self {
+        self.options.network_mount = Some(network_mount.into());
+        self
+    }
+
+    /// Enable or disable the validation of Windows file names (see
+    /// [`Options::validate_windows_names`]).
+    #[must_use]
+    pub fn validate_windows_names(mut self, validate_windows_names: bool) -> Self {
+        self.options.validate_windows_names = validate_windows_names;
+        self
+    }
+
+    /// Add the mount point of a volume without drive letter (see [`Options::volume_mounts`]).
+    #[must_use]
+    pub fn volume_mount(
+        mut self,
+        volume: impl Into<String>,
+        mount_point: impl Into<String>,
+    ) -> Self {
+        self.options
+            .volume_mounts
+            .insert(volume.into(), mount_point.into());
+        self
+    }
+
+    /// Enable or disable case-sensitive matching of drive mount points (see
+    /// [`Options::case_sensitive_mount`]).
+    #[must_use]
+    pub fn case_sensitive_mount(mut self, case_sensitive_mount: bool) -> Self {
+        self.options.case_sensitive_mount = case_sensitive_mount;
+        self
+    }
+
+    /// Enable or disable the replacement of look-alike characters (see
+    /// [`Options::lenient_unicode`]).
+    #[must_use]
+    pub fn lenient_unicode(mut self, lenient_unicode: bool) -> Self {
+        self.options.lenient_unicode = lenient_unicode;
+        self
+    }
+
+    /// Set the version of WSL that paths are converted for (see [`Options::wsl_version`]).
+    ///
+    /// Unlike [`Options::for_wsl_version`], this does not change any other options.
+    #[must_use]
+    pub fn wsl_version(mut self, wsl_version: WslVersion) -> Self {
+        self.options.wsl_version = Some(wsl_version);
+        self
+    }
+
+    /// Set the name of the WSL distribution that converted paths are used in (see
+    /// [`Options::current_distro`]).
+    #[must_use]
+    pub fn current_distro(mut self, current_distro: impl Into<String>) -> Self {
+        self.options.current_distro = Some(current_distro.into());
+        self
+    }
+
+    /// Set how network paths on unknown hosts are handled (see [`Options::on_unknown_host`]).
+    #[must_use]
+    pub fn on_unknown_host(mut self, on_unknown_host: UnknownHostPolicy) -> Self {
+        self.options.on_unknown_host = on_unknown_host;
+        self
+    }
+
+    /// Returns the configured options.
+    #[must_use]
+    pub fn build(self) -> Options {
+        self.options
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! String types that can only hold absolute WSL or Windows paths.
+
+use crate::{
+    check_windows_path_is_absolute, check_wsl_path_is_absolute, windows_to_wsl, wsl_to_windows,
+    Error,
+};
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+
+/// An absolute path inside WSL (e.g. `/mnt/c/Windows`).
+///
+/// Paths can also be parsed with [`str::parse`] or created with [`TryFrom`], which apply the
+/// same checks as [`WslPath::new`]. Whether the path is located on a Windows drive is only
+/// checked during conversion, because it depends on the [`Options`](crate::Options).
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{Error, WindowsPath, WslPath};
+///
+/// let path = WslPath::new("/mnt/c/Windows").unwrap();
+/// assert_eq!(path.to_windows().unwrap().as_ref(), "C:\\Windows");
+/// assert!(path.ends_with("Windows"));
+///
+/// assert_eq!(WslPath::new("foo/bar").unwrap_err(), Error::RelativePath);
+///
+/// let path: WslPath = "/home/user".parse().unwrap();
+/// assert_eq!(path.as_ref(), "/home/user");
+/// assert_eq!("../foo".parse::<WslPath>().unwrap_err(), Error::RelativePath);
+///
+/// let path = WslPath::try_from("/mnt/c/x").unwrap();
+/// assert_eq!(path.to_windows().unwrap().as_ref(), "C:\\x");
+/// let path: WslPath = String::from("/mnt/c/x").try_into().unwrap();
+/// assert_eq!(path.as_ref(), "/mnt/c/x");
+/// assert_eq!(WslPath::try_from("mnt/c/x").unwrap_err(), Error::RelativePath);
+/// assert_eq!(WslPath::try_from(String::from("./x")).unwrap_err(), Error::RelativePath);
+///
+/// // Windows paths are converted
+/// let windows_path = WindowsPath::new("C:\\Windows").unwrap();
+/// assert_eq!(WslPath::try_from(windows_path).unwrap().as_ref(), "/mnt/c/Windows");
+/// let windows_path = WindowsPath::new("\\\\server\\share\\foo").unwrap();
+/// assert_eq!(
+///     WslPath::try_from(windows_path).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\server\\share".to_string() },
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WslPath(String);
+
+impl WslPath {
+    /// Create a WSL path from a string.
+    ///
+    /// # Errors
+    ///
+    /// If the path is not absolute, the method returns an [`Error::RelativePath`].
+    pub fn new(path: &str) -> Result<Self, Error> {
+        check_wsl_path_is_absolute(typed_path::UnixPath::new(path))?;
+
+        Ok(Self(path.to_string()))
+    }
+
+    /// Convert the path to a Windows path.
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the same cases as [`wsl_to_windows`].
+    pub fn to_windows(&self) -> Result<WindowsPath, Error> {
+        wsl_to_windows(&self.0).map(WindowsPath)
+    }
+
+    /// Convert the path into a `String`.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for WslPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self, Error> {
+        Self::new(path)
+    }
+}
+
+impl TryFrom<&str> for WslPath {
+    type Error = Error;
+
+    fn try_from(path: &str) -> Result<Self, Error> {
+        Self::new(path)
+    }
+}
+
+impl TryFrom<String> for WslPath {
+    type Error = Error;
+
+    fn try_from(path: String) -> Result<Self, Error> {
+        check_wsl_path_is_absolute(typed_path::UnixPath::new(&path))?;
+        Ok(Self(path))
+    }
+}
+
+impl TryFrom<WindowsPath> for WslPath {
+    type Error = Error;
+
+    fn try_from(path: WindowsPath) -> Result<Self, Error> {
+        path.to_wsl()
+    }
+}
+
+impl fmt::Display for WslPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for WslPath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for WslPath {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// An absolute path on the Windows host (e.g. `C:\Windows`).
+///
+/// Paths can also be parsed with [`str::parse`] or created with [`TryFrom`], which apply the
+/// same checks as [`WindowsPath::new`]. Whether the prefix of the path can be converted is only
+/// checked during conversion, because it depends on the [`Options`](crate::Options).
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{Error, WindowsPath, WslPath};
+///
+/// let path = WindowsPath::new("C:\\Windows").unwrap();
+/// assert_eq!(path.to_wsl().unwrap().as_ref(), "/mnt/c/Windows");
+/// assert_eq!(path.to_string(), "C:\\Windows");
+///
+/// assert_eq!(WindowsPath::new("foo\\bar").unwrap_err(), Error::RelativePath);
+/// assert_eq!(WindowsPath::new("C:foo").unwrap_err(), Error::DriveRelativePath { drive: 'C' });
+///
+/// let path: WindowsPath = "\\\\wsl.localhost\\Ubuntu\\home".parse().unwrap();
+/// assert_eq!(path.to_wsl().unwrap().as_ref(), "/home");
+/// assert_eq!("Windows\\System32".parse::<WindowsPath>().unwrap_err(), Error::RelativePath);
+/// assert_eq!("D:".parse::<WindowsPath>().unwrap_err(), Error::DriveRelativePath { drive: 'D' });
+///
+/// let path = WindowsPath::try_from("C:\\x").unwrap();
+/// assert_eq!(path.to_wsl().unwrap().as_ref(), "/mnt/c/x");
+/// let path: WindowsPath = String::from("C:\\x").try_into().unwrap();
+/// assert_eq!(path.as_ref(), "C:\\x");
+/// assert_eq!(WindowsPath::try_from("x\\y").unwrap_err(), Error::RelativePath);
+/// assert_eq!(
+///     WindowsPath::try_from(String::from("C:x")).unwrap_err(),
+///     Error::DriveRelativePath { drive: 'C' },
+/// );
+///
+/// // WSL paths are converted
+/// let wsl_path = WslPath::new("/mnt/c/Windows").unwrap();
+/// assert_eq!(WindowsPath::try_from(wsl_path).unwrap().as_ref(), "C:\\Windows");
+/// let wsl_path = WslPath::new("/home/user").unwrap();
+/// assert_eq!(
+///     WindowsPath::try_from(wsl_path).unwrap_err(),
+///     Error::InvalidPrefix { prefix: "/home".to_string() },
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WindowsPath(String);
+
+impl WindowsPath {
+    /// Create a Windows path from a string.
+    ///
+    /// # Errors
+    ///
+    /// If the path is not absolute, the method returns an [`Error::RelativePath`], or an
+    /// [`Error::DriveRelativePath`] if the path is only relative to the current directory of a
+    /// drive (e.g. `C:foo`).
+    pub fn new(path: &str) -> Result<Self, Error> {
+        check_windows_path_is_absolute(typed_path::WindowsPath::new(path))?;
+        Ok(Self(path.to_string()))
+    }
+
+    /// Convert the path to a WSL path.
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the same cases as [`windows_to_wsl`].
+    pub fn to_wsl(&self) -> Result<WslPath, Error> {
+        windows_to_wsl(&self.0).map(WslPath)
+    }
+
+    /// Convert the path into a `String`.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for WindowsPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self, Error> {
+        Self::new(path)
+    }
+}
+
+impl TryFrom<&str> for WindowsPath {
+    type Error = Error;
+
+    fn try_from(path: &str) -> Result<Self, Error> {
+        Self::new(path)
+    }
+}
+
+impl TryFrom<String> for WindowsPath {
+    type Error = Error;
+
+    fn try_from(path: String) -> Result<Self, Error> {
+        check_windows_path_is_absolute(typed_path::WindowsPath::new(&path))?;
+        Ok(Self(path))
+    }
+}
+
+impl TryFrom<WslPath> for WindowsPath {
+    type Error = Error;
+
+    fn try_from(path: WslPath) -> Result<Self, Error> {
+        path.to_windows()
+    }
+}
+
+impl fmt::Display for WindowsPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for WindowsPath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for WindowsPath {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
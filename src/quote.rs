@@ -0,0 +1,106 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion of paths to quoted strings for use in shell commands.
+
+use crate::{windows_to_wsl, wsl_to_windows, Error};
+use alloc::string::String;
+
+/// Convert a Windows path to a WSL path that is quoted for use in a POSIX shell command.
+///
+/// The converted path is enclosed in single quotes, so that spaces and special characters like
+/// `$` are not interpreted by the shell. Single quotes in the path are escaped as `'\''`. This is
+/// useful to build command lines like `wsl.exe -e bash -c "ls <path>"`.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`windows_to_wsl`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::windows_to_wsl_shell_quoted;
+///
+/// assert_eq!(
+///     windows_to_wsl_shell_quoted("C:\\Program Files").unwrap(),
+///     "'/mnt/c/Program Files'",
+/// );
+/// assert_eq!(
+///     windows_to_wsl_shell_quoted("C:\\Users\\user\\$HOME").unwrap(),
+///     "'/mnt/c/Users/user/$HOME'",
+/// );
+/// assert_eq!(
+///     windows_to_wsl_shell_quoted("C:\\Users\\O'Brien").unwrap(),
+///     r"'/mnt/c/Users/O'\''Brien'",
+/// );
+/// ```
+pub fn windows_to_wsl_shell_quoted(windows_path: &str) -> Result<String, Error> {
+    let path = windows_to_wsl(windows_path)?;
+    // "/foo" (4 chars) -> "'/foo'" (6 chars)
+    let mut quoted = String::with_capacity(path.len() + 2);
+    quoted.push('\'');
+    for ch in path.chars() {
+        match ch {
+            // Single quotes cannot be escaped inside of single quotes, so the quoted string is
+            // closed, followed by an escaped quote, and opened again.
+            '\'' => quoted.push_str(r"'\''"),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('\'');
+    Ok(quoted)
+}
+
+/// Returns `true` if PowerShell treats the character as a single quote, i.e. for `'` and the
+/// typographic single quotes `‘`, `’`, `‚` and `‛`.
+fn is_powershell_single_quote(ch: char) -> bool {
+    matches!(ch, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}')
+}
+
+/// Convert a WSL path to a Windows path that is quoted for use in a PowerShell command.
+///
+/// The converted path is enclosed in single quotes, so that spaces and special characters like
+/// `$` are not interpreted by PowerShell. Single quotes in the path are escaped by doubling them,
+/// which includes the typographic quotes (e.g. `’`) that PowerShell treats as single quotes.
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`wsl_to_windows`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::wsl_to_windows_ps_quoted;
+///
+/// assert_eq!(
+///     wsl_to_windows_ps_quoted("/mnt/c/Program Files").unwrap(),
+///     r"'C:\Program Files'",
+/// );
+/// assert_eq!(
+///     wsl_to_windows_ps_quoted("/mnt/c/Users/O'Brien").unwrap(),
+///     r"'C:\Users\O''Brien'",
+/// );
+/// assert_eq!(
+///     wsl_to_windows_ps_quoted("/mnt/c/Users/O’Brien").unwrap(),
+///     r"'C:\Users\O’’Brien'",
+/// );
+/// ```
+pub fn wsl_to_windows_ps_quoted(wsl_path: &str) -> Result<String, Error> {
+    let path = wsl_to_windows(wsl_path)?;
+    // "C:\foo" (6 chars) -> "'C:\foo'" (8 chars)
+    let mut quoted = String::with_capacity(path.len() + 2);
+    quoted.push('\'');
+    for ch in path.chars() {
+        if is_powershell_single_quote(ch) {
+            quoted.push(ch);
+        }
+        quoted.push(ch);
+    }
+    quoted.push('\'');
+    Ok(quoted)
+}
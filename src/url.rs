@@ -0,0 +1,537 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Conversion of paths to URLs.
+
+use crate::{
+    check_windows_path_is_absolute, check_wsl_path_is_absolute, is_wsl_hostname,
+    windows_to_wsl_with, Error, Options,
+};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use typed_path::{
+    UnixPath, Utf8UnixComponent, Utf8UnixPath, Utf8WindowsComponent, Utf8WindowsPath,
+    Utf8WindowsPrefix, WindowsPath,
+};
+
+/// Convert a Windows path to a `file://` URL.
+///
+/// Drive paths are converted to URLs with an empty host (e.g. `file:///C:/Windows`), UNC paths
+/// use the server as host (e.g. `file://server/share/foo`). Paths on the filesystem of a WSL
+/// distribution always use the host `wsl.localhost`. The path is normalized, and all characters
+/// that may not appear in a URL path (including `%`) are percent-encoded.
+///
+/// # Errors
+///
+/// If the path is not absolute, the method returns an [`Error::RelativePath`] (or an
+/// [`Error::DriveRelativePath`] for paths like `C:foo`). Paths with prefixes that have no URL
+/// representation fail with an [`Error::InvalidPrefix`] or [`Error::DevicePath`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{windows_to_file_url, Error};
+///
+/// assert_eq!(windows_to_file_url("C:\\Windows\\System32").unwrap(), "file:///C:/Windows/System32");
+/// assert_eq!(windows_to_file_url("C:\\Program Files").unwrap(), "file:///C:/Program%20Files");
+/// assert_eq!(windows_to_file_url("D:\\").unwrap(), "file:///D:/");
+/// assert_eq!(windows_to_file_url("C:\\foo\\..\\bar\\").unwrap(), "file:///C:/bar/");
+/// assert_eq!(windows_to_file_url("\\\\?\\C:\\Windows").unwrap(), "file:///C:/Windows");
+///
+/// // Characters that already look percent-encoded are encoded again
+/// assert_eq!(windows_to_file_url("C:\\100%20.txt").unwrap(), "file:///C:/100%2520.txt");
+/// assert_eq!(windows_to_file_url("C:\\#1 sä.txt").unwrap(), "file:///C:/%231%20s%C3%A4.txt");
+///
+/// assert_eq!(
+///     windows_to_file_url("\\\\wsl$\\Ubuntu\\home\\user").unwrap(),
+///     "file://wsl.localhost/Ubuntu/home/user",
+/// );
+/// assert_eq!(
+///     windows_to_file_url("\\\\server\\My Share\\foo.txt").unwrap(),
+///     "file://server/My%20Share/foo.txt",
+/// );
+///
+/// assert_eq!(windows_to_file_url("foo\\bar.txt").unwrap_err(), Error::RelativePath);
+/// assert_eq!(
+///     windows_to_file_url("\\\\.\\COM1\\").unwrap_err(),
+///     Error::DevicePath { device: "COM1".to_string() },
+/// );
+/// ```
+pub fn windows_to_file_url(windows_path: &str) -> Result<String, Error> {
+    let path = Utf8WindowsPath::new(windows_path);
+    check_windows_path_is_absolute(WindowsPath::new(windows_path))?;
+
+    // "C:\foo" (6 chars) -> "file:///C:/foo" (14 chars)
+    let expected_length = windows_path.len() + 8;
+    let mut output = String::with_capacity(expected_length);
+    output.push_str("file://");
+    let mut root_length = output.len();
+    for component in path.components() {
+        match component {
+            Utf8WindowsComponent::Prefix(prefix_component) => {
+                match prefix_component.kind() {
+                    Utf8WindowsPrefix::VerbatimDisk(disk) | Utf8WindowsPrefix::Disk(disk) => {
+                        output.push('/');
+                        output.push(disk);
+                        output.push(':');
+                    }
+                    Utf8WindowsPrefix::Verbatim(name) | Utf8WindowsPrefix::DeviceNS(name) if matches!(name.as_bytes(), [drive, b':'] if drive.is_ascii_alphabetic()) =>
+                    {
+                        output.push('/');
+                        output.push_str(name);
+                    }
+                    Utf8WindowsPrefix::UNC(hostname, share)
+                    | Utf8WindowsPrefix::VerbatimUNC(hostname, share)
+                        if !share.is_empty() =>
+                    {
+                        if is_wsl_hostname(hostname) {
+                            output.push_str("wsl.localhost");
+                        } else {
+                            push_percent_encoded(&mut output, hostname);
+                        }
+                        output.push('/');
+                        push_percent_encoded(&mut output, share);
+                    }
+                    Utf8WindowsPrefix::DeviceNS(device) => {
+                        return Err(Error::DevicePath {
+                            device: device.to_string(),
+                        });
+                    }
+                    // UNC paths with an empty share name (e.g. `\\server\\foo`) and verbatim paths
+                    // that are not drives (e.g. `\\?\GLOBALROOT\Device\...`)
+                    Utf8WindowsPrefix::UNC(..)
+                    | Utf8WindowsPrefix::VerbatimUNC(..)
+                    | Utf8WindowsPrefix::Verbatim(_) => {
+                        return Err(Error::InvalidPrefix {
+                            prefix: prefix_component.as_str().to_string(),
+                        });
+                    }
+                }
+                root_length = output.len();
+            }
+            Utf8WindowsComponent::RootDir | Utf8WindowsComponent::CurDir => (),
+            Utf8WindowsComponent::Normal(name) => {
+                output.push('/');
+                push_percent_encoded(&mut output, name);
+            }
+            // Like on Windows, `..` components at the root of a drive or share are ignored
+            Utf8WindowsComponent::ParentDir => {
+                let parent_length = output[root_length..]
+                    .rfind('/')
+                    .map_or(root_length, |index| root_length + index);
+                output.truncate(parent_length);
+            }
+        }
+    }
+
+    if output.len() == root_length || windows_path.ends_with(['\\', '/']) {
+        output.push('/');
+    }
+
+    Ok(output)
+}
+
+/// Convert a `file://` URL to a Windows path.
+///
+/// This is the inverse of [`windows_to_file_url`]. URLs with an empty host (or `localhost`) must
+/// refer to a drive (e.g. `file:///C:/Windows`), URLs with a host are converted to UNC paths
+/// (e.g. `file://wsl.localhost/Ubuntu/home` to `\\wsl.localhost\Ubuntu\home`). The scheme is
+/// matched case-insensitively, the path is percent-decoded and a query or fragment is ignored. A
+/// trailing slash is kept as trailing separator, like [`windows_to_file_url`] does. Dot segments are removed after decoding (see [RFC 3986, section
+/// 5.2.4](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4)), but `..` never leaves the drive
+/// or share.
+///
+/// # Errors
+///
+/// If the URL does not use the `file` scheme, does not refer to a drive or share, or contains
+/// invalid percent-encoded sequences or encoded separators, the method returns an
+/// [`Error::InvalidUri`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{file_url_to_windows, windows_to_file_url, Error};
+///
+/// assert_eq!(file_url_to_windows("file:///C:/Program%20Files").unwrap(), "C:\\Program Files");
+/// assert_eq!(file_url_to_windows("FILE://localhost/d:/foo/").unwrap(), "d:\\foo\\");
+/// assert_eq!(file_url_to_windows("file:///C:/").unwrap(), "C:\\");
+/// assert_eq!(file_url_to_windows("file:///C:/a%C3%A4%231.txt#top").unwrap(), "C:\\aä#1.txt");
+/// assert_eq!(
+///     file_url_to_windows("file://wsl.localhost/Ubuntu/home/user").unwrap(),
+///     "\\\\wsl.localhost\\Ubuntu\\home\\user",
+/// );
+/// assert_eq!(file_url_to_windows("file://server/share").unwrap(), "\\\\server\\share\\");
+/// assert_eq!(file_url_to_windows("file:///C:/a/%2e%2e/%2e%2e/b").unwrap(), "C:\\b");
+/// assert_eq!(file_url_to_windows("file://server/share/./a/../../b").unwrap(), "\\\\server\\share\\b");
+///
+/// let url = windows_to_file_url("C:\\100% done\\notes.txt").unwrap();
+/// assert_eq!(file_url_to_windows(&url).unwrap(), "C:\\100% done\\notes.txt");
+///
+/// for url in [
+///     "https://example.com/C:/foo",
+///     "file:///home/user",
+///     "file://server",
+///     "file:///C:/foo%2Fbar",
+///     "file:///C:/%zz",
+///     "file://server/%2E%2E/foo",
+/// ] {
+///     assert_eq!(file_url_to_windows(url).unwrap_err(), Error::InvalidUri { uri: url.to_string() });
+/// }
+/// ```
+pub fn file_url_to_windows(url: &str) -> Result<String, Error> {
+    let invalid_uri = || Error::InvalidUri {
+        uri: url.to_string(),
+    };
+
+    let FileUrl {
+        host,
+        names,
+        trailing_separator,
+    } = parse_file_url(url).ok_or_else(invalid_uri)?;
+    let mut names = names.into_iter();
+    let mut output = String::with_capacity(url.len());
+    if host.is_empty() {
+        let drive = names
+            .next()
+            .filter(|drive| is_drive(drive))
+            .ok_or_else(invalid_uri)?;
+        output.push_str(&drive);
+    } else {
+        let share = names
+            .next()
+            .filter(|share| !matches!(share.as_str(), "." | ".."))
+            .ok_or_else(invalid_uri)?;
+        output.push_str(r"\\");
+        output.push_str(&host);
+        output.push('\\');
+        output.push_str(&share);
+    }
+    output.push('\\');
+    for name in remove_dot_segments(names) {
+        if !output.ends_with('\\') {
+            output.push('\\');
+        }
+        output.push_str(&name);
+    }
+    if trailing_separator && !output.ends_with('\\') {
+        output.push('\\');
+    }
+
+    Ok(output)
+}
+
+/// Convert a `file://` URL to a WSL path.
+///
+/// URLs that refer to a Windows drive or share are converted with [`file_url_to_windows`] and
+/// [`windows_to_wsl`], so that e.g. `file:///C:/Windows` becomes `/mnt/c/Windows` and
+/// `file://wsl.localhost/Ubuntu/home` becomes `/home`. URLs with an empty host that do not refer
+/// to a drive are WSL paths already (e.g. `file:///home/user`). Trailing slashes are kept and dot
+/// segments are removed after decoding, like in [`file_url_to_windows`].
+///
+/// # Errors
+///
+/// This function fails in the same cases as [`file_url_to_windows`] and [`windows_to_wsl`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{file_url_to_wsl, Error};
+///
+/// assert_eq!(file_url_to_wsl("file:///C:/Program%20Files").unwrap(), "/mnt/c/Program Files");
+/// assert_eq!(file_url_to_wsl("file://wsl.localhost/Ubuntu/home/user").unwrap(), "/home/user");
+/// assert_eq!(file_url_to_wsl("file:///home/user/My%20Notes.md").unwrap(), "/home/user/My Notes.md");
+/// assert_eq!(file_url_to_wsl("file:///").unwrap(), "/");
+/// assert_eq!(file_url_to_wsl("file:///home/user/").unwrap(), "/home/user/");
+/// assert_eq!(file_url_to_wsl("file:///C:/Windows/").unwrap(), "/mnt/c/Windows/");
+/// assert_eq!(file_url_to_wsl("file://wsl.localhost/Ubuntu/home/").unwrap(), "/home/");
+/// assert_eq!(file_url_to_wsl("file:///home/../etc").unwrap(), "/etc");
+/// assert_eq!(file_url_to_wsl("file:///%2E%2E/home/./user").unwrap(), "/home/user");
+/// assert_eq!(file_url_to_wsl("file:///C:/Windows/%2e%2e/%2e%2e/foo").unwrap(), "/mnt/c/foo");
+///
+/// assert_eq!(
+///     file_url_to_wsl("file://server/share/foo").unwrap_err(),
+///     Error::InvalidPrefix { prefix: "\\\\server\\share".to_string() },
+/// );
+/// assert_eq!(
+///     file_url_to_wsl("vscode-remote://wsl+Ubuntu/home").unwrap_err(),
+///     Error::InvalidUri { uri: "vscode-remote://wsl+Ubuntu/home".to_string() },
+/// );
+/// ```
+pub fn file_url_to_wsl(url: &str) -> Result<String, Error> {
+    let FileUrl {
+        host,
+        names,
+        trailing_separator,
+    } = parse_file_url(url).ok_or_else(|| Error::InvalidUri {
+        uri: url.to_string(),
+    })?;
+    if !host.is_empty() || names.first().is_some_and(|name| is_drive(name)) {
+        let options = Options::builder()
+            .preserve_trailing_separator(trailing_separator)
+            .build();
+        return windows_to_wsl_with(&file_url_to_windows(url)?, &options);
+    }
+
+    let mut output = String::with_capacity(url.len());
+    for name in remove_dot_segments(names) {
+        output.push('/');
+        output.push_str(&name);
+    }
+    if output.is_empty() || trailing_separator {
+        output.push('/');
+    }
+    Ok(output)
+}
+
+/// A `file://` URL split into the percent-decoded host and path segments.
+struct FileUrl {
+    /// The host, which is empty for `localhost`.
+    host: String,
+    /// The path segments, without empty segments.
+    names: Vec<String>,
+    /// Whether the path ends with a slash.
+    trailing_separator: bool,
+}
+
+/// Split a `file://` URL into the percent-decoded host and path segments.
+///
+/// Returns `None` if the URL is invalid or if a decoded segment contains a separator.
+fn parse_file_url(url: &str) -> Option<FileUrl> {
+    let rest = strip_prefix_ignore_ascii_case(url, "file://")?;
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let host = if host.eq_ignore_ascii_case("localhost") {
+        String::new()
+    } else {
+        percent_decode(host)?
+    };
+    let names = path
+        .split('/')
+        .filter(|name| !name.is_empty())
+        .map(|name| percent_decode(name).filter(|name| !name.contains(['/', '\\'])))
+        .collect::<Option<_>>()?;
+    Some(FileUrl {
+        host,
+        names,
+        trailing_separator: path.len() > 1 && path.ends_with('/'),
+    })
+}
+
+/// Remove the `.` and `..` segments from decoded path segments.
+///
+/// A `..` segment at the root is ignored, so that the path never leaves the root.
+fn remove_dot_segments(names: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut output = Vec::new();
+    for name in names {
+        match name.as_str() {
+            "." => (),
+            ".." => {
+                output.pop();
+            }
+            _ => output.push(name),
+        }
+    }
+    output
+}
+
+/// Returns `true` if the given path segment is a drive (e.g. `C:`).
+fn is_drive(name: &str) -> bool {
+    matches!(name.as_bytes(), [letter, b':'] if letter.is_ascii_alphabetic())
+}
+
+/// Convert a WSL path to a `vscode-remote://` URI that refers to a file in the WSL distribution
+/// named `distro`.
+///
+/// These URIs are used by the WSL extension of Visual Studio Code, e.g.
+/// `vscode-remote://wsl+Ubuntu/home/user/file.txt`. The path is normalized and percent-encoded,
+/// but not converted otherwise, so that paths below `/mnt` stay WSL paths.
+///
+/// # Errors
+///
+/// If the path is not absolute, the method returns an [`Error::RelativePath`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{wsl_to_vscode_remote, Error};
+///
+/// assert_eq!(
+///     wsl_to_vscode_remote("/home/user/file.txt", "Ubuntu").unwrap(),
+///     "vscode-remote://wsl+Ubuntu/home/user/file.txt",
+/// );
+/// assert_eq!(
+///     wsl_to_vscode_remote("/home/user/My Documents/../notes #1.md", "Ubuntu").unwrap(),
+///     "vscode-remote://wsl+Ubuntu/home/user/notes%20%231.md",
+/// );
+/// assert_eq!(
+///     wsl_to_vscode_remote("/mnt/c/Program Files", "Ubuntu-22.04").unwrap(),
+///     "vscode-remote://wsl+Ubuntu-22.04/mnt/c/Program%20Files",
+/// );
+/// assert_eq!(wsl_to_vscode_remote("/", "Ubuntu").unwrap(), "vscode-remote://wsl+Ubuntu/");
+///
+/// assert_eq!(wsl_to_vscode_remote("foo/bar.txt", "Ubuntu").unwrap_err(), Error::RelativePath);
+/// ```
+pub fn wsl_to_vscode_remote(wsl_path: &str, distro: &str) -> Result<String, Error> {
+    let path = Utf8UnixPath::new(wsl_path);
+    check_wsl_path_is_absolute(UnixPath::new(wsl_path))?;
+
+    // "/foo" (4 chars) -> "vscode-remote://wsl+<distro>/foo" (24 chars + distro length)
+    let expected_length = wsl_path.len() + distro.len() + 20;
+    let mut output = String::with_capacity(expected_length);
+    output.push_str("vscode-remote://wsl+");
+    push_percent_encoded(&mut output, distro);
+    let root_length = output.len();
+    for component in path.normalize().components() {
+        if let Utf8UnixComponent::Normal(name) = component {
+            output.push('/');
+            push_percent_encoded(&mut output, name);
+        }
+    }
+    if !output.ends_with('/') && (output.len() == root_length || wsl_path.ends_with('/')) {
+        output.push('/');
+    }
+
+    Ok(output)
+}
+
+/// Parse a `vscode-remote://wsl+<distro>/...` URI into a WSL path and the name of the WSL
+/// distribution.
+///
+/// This is the inverse of [`wsl_to_vscode_remote`](crate::wsl_to_vscode_remote). The scheme and
+/// the `wsl+` prefix of the authority are matched case-insensitively, the path and distribution
+/// name are percent-decoded and dot segments are removed from the decoded path. A query or
+/// fragment is ignored.
+///
+/// # Errors
+///
+/// If the URI does not use the `vscode-remote` scheme, does not refer to a WSL distribution or
+/// contains invalid percent-encoded sequences or encoded slashes, the method returns an
+/// [`Error::InvalidUri`].
+///
+/// # Examples
+///
+/// ```
+/// use wslpath_rs::{vscode_remote_to_wsl, wsl_to_vscode_remote, Error};
+///
+/// assert_eq!(
+///     vscode_remote_to_wsl("vscode-remote://wsl+Ubuntu/home/user").unwrap(),
+///     ("/home/user".to_string(), "Ubuntu".to_string()),
+/// );
+/// assert_eq!(
+///     vscode_remote_to_wsl("vscode-remote://wsl+Ubuntu/mnt/c/Program%20Files").unwrap(),
+///     ("/mnt/c/Program Files".to_string(), "Ubuntu".to_string()),
+/// );
+/// assert_eq!(
+///     vscode_remote_to_wsl("vscode-remote://wsl+Ubuntu").unwrap(),
+///     ("/".to_string(), "Ubuntu".to_string()),
+/// );
+/// assert_eq!(
+///     vscode_remote_to_wsl("vscode-remote://wsl+Ubuntu/home/%2e%2e/%2E%2E/etc/./passwd").unwrap(),
+///     ("/etc/passwd".to_string(), "Ubuntu".to_string()),
+/// );
+/// assert_eq!(
+///     vscode_remote_to_wsl("vscode-remote://wsl+Ubuntu/home/user/..").unwrap(),
+///     ("/home".to_string(), "Ubuntu".to_string()),
+/// );
+/// assert_eq!(
+///     vscode_remote_to_wsl("vscode-remote://wsl+Ubuntu/home/a%5Cb").unwrap(),
+///     ("/home/a\\b".to_string(), "Ubuntu".to_string()),
+/// );
+///
+/// let uri = wsl_to_vscode_remote("/home/user/notes #1.md", "Debian").unwrap();
+/// assert_eq!(
+///     vscode_remote_to_wsl(&uri).unwrap(),
+///     ("/home/user/notes #1.md".to_string(), "Debian".to_string()),
+/// );
+///
+/// for uri in [
+///     "file:///home/user",
+///     "vscode-remote://ssh-remote+server/home/user",
+///     "vscode-remote://wsl+/home/user",
+///     "vscode-remote://wsl+Ubuntu/home/%zz",
+///     "vscode-remote://wsl+Ubuntu/home/a%2Fb",
+/// ] {
+///     assert_eq!(vscode_remote_to_wsl(uri).unwrap_err(), Error::InvalidUri { uri: uri.to_string() });
+/// }
+/// ```
+pub fn vscode_remote_to_wsl(uri: &str) -> Result<(String, String), Error> {
+    let invalid_uri = || Error::InvalidUri {
+        uri: uri.to_string(),
+    };
+
+    let rest = strip_prefix_ignore_ascii_case(uri, "vscode-remote://").ok_or_else(invalid_uri)?;
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let distro = strip_prefix_ignore_ascii_case(authority, "wsl+")
+        .filter(|distro| !distro.is_empty())
+        .ok_or_else(invalid_uri)?;
+
+    let distro = percent_decode(distro).ok_or_else(invalid_uri)?;
+    // Backslashes are valid in WSL file names, but encoded slashes would change the structure of
+    // the path
+    let names: Vec<_> = path
+        .split('/')
+        .filter(|name| !name.is_empty())
+        .map(|name| percent_decode(name).filter(|name| !name.contains('/')))
+        .collect::<Option<_>>()
+        .ok_or_else(invalid_uri)?;
+    let mut wsl_path = String::with_capacity(path.len() + 1);
+    for name in remove_dot_segments(names) {
+        wsl_path.push('/');
+        wsl_path.push_str(&name);
+    }
+    if wsl_path.is_empty() || path.ends_with('/') {
+        wsl_path.push('/');
+    }
+    Ok((wsl_path, distro))
+}
+
+/// Strip a prefix from a string, ignoring ASCII case differences.
+fn strip_prefix_ignore_ascii_case<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    value
+        .get(..prefix.len())
+        .filter(|start| start.eq_ignore_ascii_case(prefix))
+        .map(|_| &value[prefix.len()..])
+}
+
+/// Decode all percent-encoded bytes in a string.
+///
+/// Returns `None` if the string contains incomplete percent-encoded sequences or does not decode
+/// to valid UTF-8.
+pub(crate) fn percent_decode(value: &str) -> Option<String> {
+    let mut output = Vec::with_capacity(value.len());
+    let mut bytes = value.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let high = char::from(bytes.next()?).to_digit(16)?;
+            let low = char::from(bytes.next()?).to_digit(16)?;
+            output.push(u8::try_from(high << 4 | low).ok()?);
+        } else {
+            output.push(byte);
+        }
+    }
+    String::from_utf8(output).ok()
+}
+
+/// Append a string to a URL, percent-encoding all bytes that are not allowed in a path segment.
+///
+/// Only unreserved characters and the sub-delimiters, `:` and `@` are kept as-is (see [RFC 3986,
+/// section 3.3](https://www.rfc-editor.org/rfc/rfc3986#section-3.3)).
+pub(crate) fn push_percent_encoded(output: &mut String, segment: &str) {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+            output.push(char::from(byte));
+        } else {
+            output.push('%');
+            output.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+            output.push(char::from(HEX_DIGITS[usize::from(byte & 0xF)]));
+        }
+    }
+}
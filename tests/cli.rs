@@ -0,0 +1,172 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Tests of the exit status and error messages of the command-line tool.
+
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Run the command-line tool with the arguments and the input on stdin.
+fn run(args: &[&str], stdin: &str) -> Output {
+    run_bytes(args, stdin.as_bytes())
+}
+
+/// Run the command-line tool with the arguments and input on stdin that is not necessarily valid
+/// UTF-8.
+fn run_bytes(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wslpath-rs"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn converted_paths_exit_with_success() {
+    let output = run(&["-u", "C:\\Windows"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"/mnt/c/Windows\n");
+
+    let output = run(&["-w", "/mnt/c/Windows"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"C:\\Windows\n");
+}
+
+#[test]
+fn relative_paths_are_translated_to_relative_paths() {
+    let output = run(&["-u", "foo\\..\\bar"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"foo/../bar\n");
+
+    let output = run(&["-w", "foo/../bar"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"foo\\..\\bar\n");
+
+    let output = run(&["-m", "foo/bar"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"foo/bar\n");
+
+    let output = run(&["-w", "foo/a\\b"], "");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn mixed_paths_use_forward_slashes() {
+    let output = run(&["-m", "/mnt/c/Program Files/foo"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"C:/Program Files/foo\n");
+
+    let output = run(&["-m", "/mnt/d"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"D:/\n");
+}
+
+#[test]
+fn absolute_flag_resolves_relative_paths_against_the_current_directory() {
+    let current_dir = std::env::current_dir().unwrap();
+    let expected = format!("{}\n", current_dir.join("foo/bar").display());
+    for args in [&["-a", "foo\\bar"], &["-au", "foo/bar"]] {
+        let output = run(args, "");
+        assert_eq!(output.status.code(), Some(0), "{args:?}");
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            expected,
+            "{args:?}"
+        );
+    }
+
+    let output = run(&["-a", "-u", "C:\\Windows"], "");
+    assert_eq!(output.stdout, b"/mnt/c/Windows\n");
+    let output = run(&["-a", "-w", "/mnt/c/Windows"], "");
+    assert_eq!(output.stdout, b"C:\\Windows\n");
+    let output = run(&["-a", "-m", "/mnt/c/Windows"], "");
+    assert_eq!(output.stdout, b"C:/Windows\n");
+}
+
+#[test]
+fn invalid_arguments_exit_with_1() {
+    let output = run(&["-x", "C:\\Windows"], "");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn paths_relative_to_a_drive_or_root_exit_with_2() {
+    for args in [&["-u", "C:foo"], &["-u", "\\foo\\bar"], &["-au", "C:foo"]] {
+        let output = run(args, "");
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+    }
+}
+
+#[test]
+fn invalid_prefixes_exit_with_3_and_name_the_prefix() {
+    let output = run(&["-u", "\\\\server\\share\\foo"], "");
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("`\\\\server\\share`"), "{stderr}");
+    assert!(stderr.contains("wsl.localhost"), "{stderr}");
+
+    let output = run(&["-u", "\\\\.\\PhysicalDrive0\\"], "");
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = run(&["-w", "/home/user"], "");
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("`/home`"), "{stderr}");
+    assert!(stderr.contains("/mnt/c"), "{stderr}");
+}
+
+#[test]
+fn stdin_exits_with_the_status_of_the_first_failure() {
+    let output = run(
+        &["-w", "--stdin"],
+        "/mnt/c/foo\n/home/user\nfoo\n/mnt/d/bar\n",
+    );
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(output.stdout, b"C:\\foo\nfoo\nD:\\bar\n");
+
+    let output = run(&["-u", "--stdin"], "C:foo\n\\\\server\\share\\foo\n");
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run(&["-w", "--stdin"], "/mnt/c/foo\n");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn stdin_paths_can_be_separated_by_nul() {
+    let output = run(
+        &["-w", "-0", "--stdin"],
+        "/mnt/c/new\nline.txt\0/mnt/d/foo\0",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"C:\\new\nline.txt\0D:\\foo\0");
+
+    let output = run(&["-u", "--null", "--stdin"], "C:\\foo\r\0");
+    assert_eq!(output.stdout, b"/mnt/c/foo\r\0");
+}
+
+#[test]
+fn stdin_paths_can_be_separated_by_crlf() {
+    let output = run(&["-w", "--stdin"], "/mnt/c/foo\r\n/mnt/d/bar\r\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"C:\\foo\nD:\\bar\n");
+}
+
+#[test]
+fn stdin_reports_invalid_utf8_and_converts_later_paths() {
+    let output = run_bytes(&["-w", "--stdin"], b"/mnt/c/\xff\n/mnt/d/bar\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.stdout, b"D:\\bar\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("/mnt/c/\u{FFFD}"), "{stderr}");
+}
@@ -0,0 +1,364 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Randomized tests that feed arbitrary input to the conversion functions to make sure that they
+//! return an error instead of panicking.
+//!
+//! Failing inputs are shrunk and saved in `proptest-regressions`, so that they are tried first in
+//! later runs. Set `PROPTEST_CASES` to run more or fewer cases.
+
+use proptest::bool::weighted;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::sample::{select, Index};
+use std::borrow::Cow;
+use typed_path::{Utf8Component, Utf8UnixPath, Utf8UnixPathBuf};
+use wslpath_rs::{
+    all_wsl_forms, classify, convert, cygwin_to_windows, file_url_to_windows, file_url_to_wsl,
+    is_convertible_to_windows, is_convertible_to_wsl, paths_equal_windows, paths_equal_wsl,
+    relative_wsl_path, split_windows, split_wsl, vscode_remote_to_wsl, windows_to_cygwin,
+    windows_to_file_url, windows_to_wsl, windows_to_wsl_bytes, windows_to_wsl_bytes_with,
+    windows_to_wsl_cow, windows_to_wsl_detailed_with, windows_to_wsl_path, windows_to_wsl_relative,
+    windows_to_wsl_shell_quoted, windows_to_wsl_with, windows_to_wsl_with_table,
+    windows_to_wsl_with_table_with, wsl_to_vscode_remote, wsl_to_windows, wsl_to_windows_as,
+    wsl_to_windows_bytes, wsl_to_windows_bytes_with, wsl_to_windows_detailed_with,
+    wsl_to_windows_ps_quoted, wsl_to_windows_unc_with, wsl_to_windows_with,
+    wsl_to_windows_with_home, wsl_to_windows_with_table, wsl_to_windows_with_table_with, DriveCase,
+    MountTable, Options, PathClassification, Separator, UnknownHostPolicy, VerbatimMode,
+};
+
+/// The number of random inputs per test.
+const CASES: u32 = 10_000;
+
+/// Fragments that random inputs are assembled from, chosen to hit prefixes, separators and
+/// special components.
+const FRAGMENTS: &[&str] = &[
+    "\\",
+    "/",
+    "\\\\",
+    "//",
+    ":",
+    "?",
+    ".",
+    "..",
+    "C",
+    "c",
+    "5",
+    "ä",
+    "$",
+    "%",
+    "%2",
+    "%zz",
+    "%20",
+    "mnt",
+    "wsl",
+    "wslg",
+    "wsl$",
+    "wsl.localhost",
+    "localhost",
+    "UNC",
+    "Volume{",
+    "}",
+    "cygdrive",
+    "CON",
+    "nul.txt",
+    " ",
+    "\0",
+    "\u{1F600}",
+    "file://",
+    "vscode-remote://",
+    "wsl+",
+    "a",
+    "foo",
+    "#",
+    "\r",
+];
+
+/// Random strings assembled from [`FRAGMENTS`].
+fn string() -> impl Strategy<Value = String> {
+    vec(select(FRAGMENTS), 0..12).prop_map(|fragments| fragments.concat())
+}
+
+/// Random strings with up to two arbitrary bytes inserted, so that they are not necessarily valid
+/// UTF-8.
+fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    (string(), vec((any::<Index>(), any::<u8>()), 0..3)).prop_map(|(string, insertions)| {
+        let mut bytes = string.into_bytes();
+        for (index, byte) in insertions {
+            bytes.insert(index.index(bytes.len() + 1), byte);
+        }
+        bytes
+    })
+}
+
+/// Random options, with random strings for the mount points and names.
+fn options() -> impl Strategy<Value = Options> {
+    let flags = (
+        select(&["/mnt", "", "/", "/cygdrive/", "mnt//x"][..]),
+        weighted(0.75),
+        any::<[bool; 7]>(),
+        select(
+            &[
+                VerbatimMode::Never,
+                VerbatimMode::Always,
+                VerbatimMode::Auto,
+            ][..],
+        ),
+        select(&[Separator::Backslash, Separator::ForwardSlash][..]),
+        select(&[DriveCase::Lower, DriveCase::Upper, DriveCase::Preserve][..]),
+        select(&[UnknownHostPolicy::Error, UnknownHostPolicy::Passthrough][..]),
+    );
+    let names = (
+        option::of(string()),
+        option::of((string(), string())),
+        option::of(string()),
+    );
+    (flags, names).prop_map(
+        |(
+            (mount_root, automount_enabled, flags, verbatim, separator, drive_case, on_unknown_host),
+            (network_mount, volume_mount, current_distro),
+        )| {
+            let [strict, normalize, preserve_trailing_separator, validate_windows_names, case_sensitive_mount, lenient_unicode, admin_share] =
+                flags;
+            let mut options = Options::default();
+            options.mount_root = mount_root.to_string();
+            options.automount_enabled = automount_enabled;
+            options.strict = strict;
+            options.normalize = normalize;
+            options.preserve_trailing_separator = preserve_trailing_separator;
+            options.verbatim = verbatim;
+            options.separator = separator;
+            options.drive_case = drive_case;
+            options.validate_windows_names = validate_windows_names;
+            options.case_sensitive_mount = case_sensitive_mount;
+            options.lenient_unicode = lenient_unicode;
+            if admin_share {
+                options.admin_share_hosts.push("localhost".to_string());
+            }
+            options.network_mount = network_mount;
+            if let Some((volume, mount_point)) = volume_mount {
+                options.volume_mounts.insert(volume, mount_point);
+            }
+            options.current_distro = current_distro;
+            options.on_unknown_host = on_unknown_host;
+            options
+        },
+    )
+}
+
+/// A mount table with random entries, most of which are rejected by [`MountTable::push`].
+fn table() -> impl Strategy<Value = MountTable> {
+    (string(), string(), string(), string(), string()).prop_map(
+        |(mount_point, target, absolute_mount_point, absolute_target, drive_mount_point)| {
+            let mut table = MountTable::new();
+            let _ = table.push(mount_point, target);
+            let _ = table.push(
+                format!("/{absolute_mount_point}"),
+                format!("C:\\{absolute_target}"),
+            );
+            let _ = table.push_drive('Z', drive_mount_point);
+            table
+        },
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn string_conversions_do_not_panic(
+        path in string(),
+        other in string(),
+        options in options(),
+        table in table(),
+    ) {
+        let _ = table.drive_mount_point('z');
+
+        prop_assert_eq!(
+            is_convertible_to_wsl(&path),
+            windows_to_wsl(&path).is_ok(),
+            "{:?}", path
+        );
+        if windows_to_wsl(&path).is_ok() && !path.starts_with('/') {
+            prop_assert!(
+                matches!(
+                    classify(&path),
+                    PathClassification::WindowsDrive { .. }
+                        | PathClassification::WslLocalhost { .. }
+                ),
+                "{:?}", path
+            );
+        }
+        if wsl_to_windows(&path).is_ok() {
+            prop_assert!(
+                matches!(classify(&path), PathClassification::WslDrive { .. }),
+                "{:?}", path
+            );
+        }
+        if let Ok(converted) = windows_to_wsl(&path) {
+            let joined: Utf8UnixPathBuf = windows_to_wsl_path(&path)
+                .unwrap()
+                .components()
+                .map(|component| component.as_str())
+                .collect();
+            prop_assert_eq!(joined.as_str(), converted, "{:?}", path);
+        }
+        prop_assert_eq!(
+            windows_to_wsl_cow(&path).map(Cow::into_owned),
+            windows_to_wsl(&path),
+            "{:?}", path
+        );
+        prop_assert_eq!(
+            is_convertible_to_windows(&path),
+            wsl_to_windows(&path).is_ok(),
+            "{:?}", path
+        );
+        for windows_path in [path.clone(), format!("C:\\{path}")] {
+            if let Ok(converted) = windows_to_wsl_with(&windows_path, &options) {
+                // Paths on unknown hosts may be passed through unchanged
+                if options.normalize
+                    && !options.preserve_trailing_separator
+                    && converted != windows_path
+                {
+                    let normalized = Utf8UnixPath::new(&converted).normalize();
+                    prop_assert_eq!(
+                        normalized.as_str(),
+                        converted,
+                        "{:?} {:?}", windows_path, options
+                    );
+                }
+            }
+        }
+        let _ = wsl_to_windows_with(&path, &options);
+        let _ = windows_to_wsl_detailed_with(&path, &options);
+        let _ = wsl_to_windows_detailed_with(&path, &options);
+        if let Ok(conversion) = windows_to_wsl_detailed_with(&path, &options) {
+            let _ = wsl_to_windows_as(&conversion.path, conversion.prefix);
+        }
+        let _ = wsl_to_windows_unc_with(&path, &other, &options);
+        let _ = all_wsl_forms(&path, &other);
+        let _ = wsl_to_windows_with_home(&path, &other, "Ubuntu");
+        if let Ok(converted) = windows_to_wsl(&path) {
+            let quoted = windows_to_wsl_shell_quoted(&path).unwrap();
+            let unquoted = quoted[1..quoted.len() - 1].replace(r"'\''", "'");
+            prop_assert_eq!(unquoted, converted, "{:?}", path);
+        }
+        if let Ok(converted) = wsl_to_windows(&path) {
+            let quoted = wsl_to_windows_ps_quoted(&path).unwrap();
+            let unquoted = quoted[1..quoted.len() - 1].replace("''", "'");
+            prop_assert_eq!(unquoted, converted, "{:?}", path);
+        }
+        let _ = convert(&path);
+        let _ = windows_to_cygwin(&path);
+        let _ = cygwin_to_windows(&path);
+        let _ = windows_to_wsl_relative(&path, &other);
+        let _ = relative_wsl_path(&path, &other);
+        let _ = windows_to_file_url(&path);
+        let _ = wsl_to_vscode_remote(&path, &other);
+        let _ = vscode_remote_to_wsl(&path);
+        let _ = file_url_to_windows(&path);
+        let _ = file_url_to_wsl(&path);
+        prop_assert!(paths_equal_windows(&path, &path), "{:?}", path);
+        prop_assert!(paths_equal_wsl(&path, &path), "{:?}", path);
+        let _ = paths_equal_windows(&path, &other);
+        let _ = paths_equal_wsl(&path, &other);
+        let _ = split_windows(&path);
+        let _ = split_wsl(&path);
+        let _ = windows_to_wsl_with_table(&path, &table);
+        let _ = wsl_to_windows_with_table(&path, &table);
+        let _ = windows_to_wsl_with_table_with(&path, &table, &options);
+        let _ = wsl_to_windows_with_table_with(&path, &table, &options);
+    }
+
+    #[test]
+    fn byte_conversions_do_not_panic(
+        path in bytes(),
+        string_path in string(),
+        options in options(),
+    ) {
+        let _ = windows_to_wsl_bytes(&path);
+        let _ = wsl_to_windows_bytes(&path);
+
+        // Byte strings that are valid UTF-8 are converted like strings
+        let path = string_path;
+        prop_assert_eq!(
+            windows_to_wsl_bytes_with(path.as_bytes(), &options),
+            windows_to_wsl_with(&path, &options).map(String::into_bytes),
+            "{:?}", path
+        );
+        prop_assert_eq!(
+            wsl_to_windows_bytes_with(path.as_bytes(), &options),
+            wsl_to_windows_with(&path, &options).map(String::into_bytes),
+            "{:?}", path
+        );
+    }
+}
+
+/// Prefixes of Windows paths that are convertible with [`prefix_options`].
+const WINDOWS_PREFIXES: &[&str] = &[
+    "C:\\",
+    "c:/",
+    "\\\\?\\C:\\",
+    "\\\\.\\D:\\",
+    "\\\\wsl.localhost\\Ubuntu\\",
+    "\\\\?\\UNC\\wsl$\\Ubuntu\\",
+    "//wsl.localhost/Ubuntu/",
+    "\\\\server\\share\\",
+    "\\\\localhost\\c$\\",
+];
+
+/// Prefixes of WSL paths that are convertible with [`prefix_options`].
+const WSL_PREFIXES: &[&str] = &[
+    "/mnt/c/",
+    "/mnt/C",
+    "//mnt//d//",
+    "/mnt/network/server/share/",
+    "/mnt/network/?/share/",
+    "/mnt/network/server/?/",
+];
+
+/// Options with a network mount and administrative shares, so that all prefixes are convertible.
+fn prefix_options() -> impl Strategy<Value = Options> {
+    options().prop_map(|mut options| {
+        options.mount_root = "/mnt".to_string();
+        options.automount_enabled = true;
+        options.network_mount = Some("/mnt/network".to_string());
+        options.admin_share_hosts.push("localhost".to_string());
+        options
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    /// The conversions check in debug builds that their output starts with a prefix of the expected
+    /// kind, so this panics if a prefix is converted incorrectly.
+    #[test]
+    fn conversions_keep_the_prefix(
+        options in prefix_options(),
+        windows_prefix in select(WINDOWS_PREFIXES),
+        wsl_prefix in select(WSL_PREFIXES),
+        windows_rest in string(),
+        wsl_rest in string(),
+    ) {
+        let path = format!("{windows_prefix}{windows_rest}");
+        if let Ok(converted) = windows_to_wsl_with(&path, &options) {
+            prop_assert!(converted.starts_with('/'), "{:?} {:?}", path, options);
+        }
+
+        let path = format!("{wsl_prefix}{wsl_rest}");
+        if let Ok(converted) = wsl_to_windows_with(&path, &options) {
+            prop_assert!(
+                matches!(converted.as_bytes(), [b'A'..=b'Z' | b'a'..=b'z', b':', ..])
+                    || converted.starts_with(['\\', '/']),
+                "{:?} {:?}", path, options
+            );
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Tests of the serialization of errors, options and conversions.
+
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use wslpath_rs::{
+    windows_to_wsl_detailed_with, wsl_to_windows_detailed, Conversion, Error, Options, Separator,
+    VerbatimMode, WslVersion,
+};
+
+#[test]
+fn errors_round_trip_with_a_kind_tag() {
+    let errors = [
+        (Error::RelativePath, json!({ "kind": "RelativePath" })),
+        (
+            Error::InvalidPrefix {
+                prefix: "\\\\server\\share".to_string(),
+            },
+            json!({ "kind": "InvalidPrefix", "prefix": "\\\\server\\share" }),
+        ),
+        (
+            Error::DriveRelativePath { drive: 'C' },
+            json!({ "kind": "DriveRelativePath", "drive": "C" }),
+        ),
+        (
+            Error::AutomountDisabled,
+            json!({ "kind": "AutomountDisabled" }),
+        ),
+        (Error::InvalidUtf8, json!({ "kind": "InvalidUtf8" })),
+        (Error::AmbiguousPath, json!({ "kind": "AmbiguousPath" })),
+        (Error::EscapesRoot, json!({ "kind": "EscapesRoot" })),
+        (
+            Error::InvalidDriveLetter {
+                got: "5".to_string(),
+            },
+            json!({ "kind": "InvalidDriveLetter", "got": "5" }),
+        ),
+        (
+            Error::InvalidWindowsComponent {
+                component: "a:b".to_string(),
+                ch: ':',
+            },
+            json!({ "kind": "InvalidWindowsComponent", "component": "a:b", "ch": ":" }),
+        ),
+        (
+            Error::ReservedWindowsName {
+                component: "NUL".to_string(),
+            },
+            json!({ "kind": "ReservedWindowsName", "component": "NUL" }),
+        ),
+        (
+            Error::AmbiguousWindowsName {
+                component: "foo.".to_string(),
+            },
+            json!({ "kind": "AmbiguousWindowsName", "component": "foo." }),
+        ),
+        (
+            Error::DevicePath {
+                device: "COM1".to_string(),
+            },
+            json!({ "kind": "DevicePath", "device": "COM1" }),
+        ),
+        (
+            Error::WslInternalMount {
+                mount: "/mnt/wsl".to_string(),
+            },
+            json!({ "kind": "WslInternalMount", "mount": "/mnt/wsl" }),
+        ),
+        (
+            Error::InvalidUri {
+                uri: "file://".to_string(),
+            },
+            json!({ "kind": "InvalidUri", "uri": "file://" }),
+        ),
+        (Error::EmptyPath, json!({ "kind": "EmptyPath" })),
+        (
+            Error::UnknownUserHome {
+                user: "user".to_string(),
+            },
+            json!({ "kind": "UnknownUserHome", "user": "user" }),
+        ),
+        (
+            Error::ForeignDistro {
+                distro: "Debian".to_string(),
+            },
+            json!({ "kind": "ForeignDistro", "distro": "Debian" }),
+        ),
+    ];
+
+    for (error, expected) in errors {
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value, expected, "{error:?}");
+        assert_eq!(serde_json::from_value::<Error>(value).unwrap(), error);
+    }
+}
+
+#[test]
+fn options_round_trip() {
+    let mut options = Options::builder()
+        .mount_root("/windows")
+        .network_mount("/mnt/network")
+        .current_distro("Ubuntu")
+        .separator(Separator::ForwardSlash)
+        .build();
+    options.verbatim = VerbatimMode::Auto;
+    options.wsl_version = Some(WslVersion::Wsl2);
+    options.volume_mounts.insert(
+        "12345678-90ab-cdef-1234-567890abcdef".to_string(),
+        "/mnt/data".to_string(),
+    );
+
+    let json = serde_json::to_string(&options).unwrap();
+    assert_eq!(serde_json::from_str::<Options>(&json).unwrap(), options);
+}
+
+#[test]
+fn missing_options_use_the_defaults() {
+    let options: Options = serde_json::from_value(json!({ "mount_root": "/windows" })).unwrap();
+    let mut expected = Options::default();
+    expected.mount_root = "/windows".to_string();
+    assert_eq!(options, expected);
+}
+
+#[test]
+fn conversions_round_trip() {
+    let options = Options::for_wsl_version(WslVersion::Wsl1);
+    let conversions = [
+        windows_to_wsl_detailed_with("C:\\Windows", &options).unwrap(),
+        windows_to_wsl_detailed_with("\\\\wsl$\\Ubuntu\\home", &options).unwrap(),
+        wsl_to_windows_detailed("/mnt/d/foo").unwrap(),
+    ];
+    for conversion in conversions {
+        let json = serde_json::to_string(&conversion).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Conversion>(&json).unwrap(),
+            conversion
+        );
+    }
+
+    let conversion = wsl_to_windows_detailed("/mnt/d/foo").unwrap();
+    assert_eq!(
+        serde_json::to_value(&conversion).unwrap(),
+        json!({
+            "path": "D:\\foo",
+            "kind": "Drive",
+            "drive": "D",
+            "distro": null,
+            "was_unc": false,
+            "wsl_version": null,
+            "prefix": "Disk",
+        }),
+    );
+}